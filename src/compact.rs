@@ -0,0 +1,114 @@
+//! Compact wind loads file format
+//!
+//! The time series of each source are stored as a single flat vector of samples
+//! instead of a vector of samples, reducing the size and the parsing time of the pickle files
+
+use crate::{
+    create_file, loads_from_name, open_file, source_name, Loads, Result, Units, WindLoads,
+    WindLoadsError, PICKLE_READER_CAPACITY,
+};
+use serde::{Deserialize, Serialize};
+use serde_pickle as pkl;
+use std::{
+    convert::TryFrom,
+    io::{BufReader, BufWriter, Write},
+    path::Path,
+};
+
+/// Wind loads of a single source stored as a flat vector
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CompactLoads {
+    /// wind loads source name
+    pub name: String,
+    /// number of samples
+    pub n_samples: usize,
+    /// length of each sample
+    pub width: usize,
+    /// concatenated samples
+    pub data: Vec<f64>,
+}
+impl TryFrom<&Loads> for CompactLoads {
+    type Error = WindLoadsError;
+    /// Flattens the loads, all the samples must have the same length
+    fn try_from(loads: &Loads) -> Result<Self> {
+        let io = loads.as_slice();
+        let width = io.first().map_or(0, |x| x.len());
+        if let Some(index) = io.iter().position(|x| x.len() != width) {
+            return Err(WindLoadsError::Jagged {
+                source: loads.name().to_string(),
+                index,
+            });
+        }
+        Ok(Self {
+            name: loads.name().to_string(),
+            n_samples: io.len(),
+            width,
+            data: io.concat(),
+        })
+    }
+}
+impl TryFrom<CompactLoads> for Loads {
+    type Error = WindLoadsError;
+    /// Reshapes the flat vector into `n_samples` samples of length `width`
+    fn try_from(compact: CompactLoads) -> Result<Self> {
+        let CompactLoads {
+            name,
+            n_samples,
+            width,
+            data,
+        } = compact;
+        let source = source_name(&name).ok_or(WindLoadsError::UnknownSource(name))?;
+        if data.len() != n_samples * width {
+            return Err(WindLoadsError::Reshape {
+                source: source.to_string(),
+                len: data.len(),
+                dofs_per_sample: width,
+            });
+        }
+        let io = if width == 0 {
+            vec![vec![]; n_samples]
+        } else {
+            data.chunks(width).map(|x| x.to_vec()).collect()
+        };
+        loads_from_name(source, io).ok_or_else(|| WindLoadsError::UnknownSource(source.to_string()))
+    }
+}
+
+/// Wind loads file with the time series stored as [`CompactLoads`]
+#[derive(Deserialize, Serialize)]
+struct CompactWindLoads {
+    outputs: Vec<Option<CompactLoads>>,
+    time: Vec<f64>,
+    #[serde(default)]
+    units: Units,
+}
+
+impl WindLoads {
+    /// Writes the wind loads to a pickle file with the time series stored as [`CompactLoads`]
+    pub fn to_compact_pickle<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let compact = CompactWindLoads {
+            outputs: self
+                .loads
+                .iter()
+                .map(|x| x.as_ref().map(CompactLoads::try_from).transpose())
+                .collect::<Result<Vec<_>>>()?,
+            time: self.time.clone(),
+            units: self.units,
+        };
+        let mut w = BufWriter::new(create_file(path)?);
+        pkl::to_writer(&mut w, &compact, true)?;
+        w.flush()?;
+        Ok(())
+    }
+    /// Reads the wind loads from a pickle file written with [`to_compact_pickle`](WindLoads::to_compact_pickle)
+    pub fn from_compact_pickle<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let r = BufReader::with_capacity(PICKLE_READER_CAPACITY, open_file(path)?);
+        let compact: CompactWindLoads = pkl::from_reader(r)?;
+        let loads = compact
+            .outputs
+            .into_iter()
+            .map(|x| x.map(Loads::try_from).transpose())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(WindLoads::from_loads(compact.time, loads).with_units(compact.units))
+    }
+}
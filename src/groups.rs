@@ -0,0 +1,86 @@
+//! Wind loads grouped by telescope subsystem
+//!
+//! The loads of the wind loads sources of a subsystem are concatenated sample by sample
+//! in the order of the source names listed in [`MOUNT_LOADS`], [`M1_LOADS`] and [`M2_LOADS`]
+
+use crate::{Result, WindLoads, WindLoadsError};
+
+/// Wind loads sources of the mount, in concatenation order
+pub const MOUNT_LOADS: [&str; 5] = [
+    "OSS_TopEnd_6F",
+    "OSS_Truss_6F",
+    "OSS_GIR_6F",
+    "OSS_CRING_6F",
+    "OSS_Cell_lcl_6F",
+];
+/// Wind loads sources of M1, in concatenation order
+pub const M1_LOADS: [&str; 1] = ["OSS_M1_lcl_6F"];
+/// Wind loads sources of M2, in concatenation order
+pub const M2_LOADS: [&str; 1] = ["MC_M2_lcl_force_6F"];
+
+/// Wind loads grouped by subsystem
+///
+/// Each field is a time vector of the concatenated samples of the subsystem sources
+#[derive(Debug, Clone, Default)]
+pub struct WindLoadsGroups {
+    pub mount: Vec<Vec<f64>>,
+    pub m1: Vec<Vec<f64>>,
+    pub m2: Vec<Vec<f64>>,
+}
+impl WindLoadsGroups {
+    /// Returns the number of time samples
+    pub fn len(&self) -> usize {
+        self.mount.len()
+    }
+    /// Returns true if there is no time sample
+    pub fn is_empty(&self) -> bool {
+        self.mount.is_empty()
+    }
+    /// Returns the mount, M1 and M2 loads at time sample `index`
+    pub fn sample(&self, index: usize) -> Option<(&[f64], &[f64], &[f64])> {
+        Some((
+            self.mount.get(index)?,
+            self.m1.get(index)?,
+            self.m2.get(index)?,
+        ))
+    }
+}
+
+impl WindLoads {
+    /// Builds the wind loads grouped by subsystem, see [`WindLoadsGroups`]
+    ///
+    /// All the sources of each subsystem must be present and the time series are truncated
+    /// to the number of samples set with [`n_sample`](WindLoads::n_sample)
+    pub fn build_grouped(self) -> Result<WindLoadsGroups> {
+        let n_sample = self.n_sample.unwrap_or(self.len()?);
+        let group = |names: &[&str]| -> Result<Vec<Vec<f64>>> {
+            let mut samples = vec![vec![]; n_sample];
+            for name in names {
+                let loads = self
+                    .loads
+                    .iter()
+                    .filter_map(|x| x.as_ref())
+                    .find(|x| x.name() == *name)
+                    .ok_or_else(|| WindLoadsError::MissingSource(name.to_string()))?;
+                let data = loads.as_slice();
+                if data.len() < n_sample {
+                    return Err(WindLoadsError::Mismatch {
+                        expected: n_sample,
+                        got: data.len(),
+                        source: name.to_string(),
+                    });
+                }
+                samples
+                    .iter_mut()
+                    .zip(data)
+                    .for_each(|(s, x)| s.extend_from_slice(x));
+            }
+            Ok(samples)
+        };
+        Ok(WindLoadsGroups {
+            mount: group(&MOUNT_LOADS)?,
+            m1: group(&M1_LOADS)?,
+            m2: group(&M2_LOADS)?,
+        })
+    }
+}
@@ -9,22 +9,130 @@
 //!  - the M2 segments
 //!  - the top-end
 
+mod compact;
+mod groups;
+mod partial;
+mod signal;
+mod stream;
+mod units;
+pub use compact::CompactLoads;
+pub use groups::{WindLoadsGroups, M1_LOADS, M2_LOADS, MOUNT_LOADS};
+pub use stream::WindLoadsStream;
+pub use units::{ForceUnit, MomentUnit, Units};
+
 use dosio::{
     io::{jar, Tags},
     DOSIOSError, Dos, IOTags, IO,
 };
-use serde;
-use serde::Deserialize;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
 use serde_pickle as pkl;
-use std::{fmt, fs::File, io, io::BufReader, path::Path};
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    fmt,
+    fs::File,
+    io,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    sync::Arc,
+};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum WindLoadsError {
     Len,
     Empty,
-    FileNotFound(io::Error),
-    PickleRead(serde_pickle::Error),
+    Jagged {
+        source: String,
+        index: usize,
+    },
+    UnknownSource(String),
+    SamplingFrequency,
+    InvalidStream,
+    UnsupportedTag,
+    UnknownUnit(String),
+    Layout {
+        source: String,
+        width: usize,
+        dofs_per_body: usize,
+    },
+    BodyIndex {
+        source: String,
+        index: usize,
+        n_body: usize,
+    },
+    MissingSource(String),
+    DuplicateSource(String),
+    AlreadySelected(String),
+    UnorderedSource(String),
+    Mismatch {
+        expected: usize,
+        got: usize,
+        source: String,
+    },
+    NonFinite {
+        source: String,
+        index: usize,
+        component: usize,
+    },
+    BodyCount {
+        source: String,
+        n_body: usize,
+        n_position: usize,
+    },
+    SourceBodies {
+        source: String,
+        expected: usize,
+        got: usize,
+    },
+    IndexRange {
+        min_index: usize,
+        max_index: usize,
+        n_sample: usize,
+    },
+    Width {
+        source: String,
+        expected: usize,
+        got: usize,
+    },
+    Reshape {
+        source: String,
+        len: usize,
+        dofs_per_sample: usize,
+    },
+    Component {
+        source: String,
+        component: usize,
+        width: usize,
+    },
+    Nfft {
+        nfft: usize,
+        n_sample: usize,
+    },
+    NonUniformTime {
+        index: usize,
+        step: f64,
+        expected: f64,
+    },
+    FileNotFound(Arc<io::Error>),
+    /// The wind loads file at `path` could not be opened or created
+    FileAccess {
+        path: String,
+        source: Arc<io::Error>,
+    },
+    PickleRead(Arc<serde_pickle::Error>),
+    Decompression(Arc<io::Error>),
+    /// The wind loads stream file could not be read, e.g. it is truncated
+    StreamRead(Arc<io::Error>),
+    JsonRead(Arc<serde_json::Error>),
+    /// The wind loading outputs could not be produced
+    ///
+    /// Currently unused and reserved: [`Dos::outputs`] returns an `Option` and
+    /// signals the end of the time series with `None` rather than with an error
     Outputs,
+    /// The wind loading takes no inputs, returned by [`Dos::inputs`]
     Inputs,
 }
 impl fmt::Display for WindLoadsError {
@@ -32,59 +140,368 @@ impl fmt::Display for WindLoadsError {
         match self {
             Self::Len => f.write_str("couldn't get the number of sample in the time series"),
             Self::Empty => f.write_str("no data available"),
+            Self::Jagged { source, index } => write!(
+                f,
+                "{} sample #{} has a different length than the previous samples",
+                source, index
+            ),
+            Self::UnknownSource(source) => write!(f, "unknown wind loads source: {}", source),
+            Self::SamplingFrequency => {
+                f.write_str("couldn't estimate the sampling frequency from the time vector")
+            }
+            Self::InvalidStream => f.write_str("not a wind loads stream file"),
+            Self::UnsupportedTag => f.write_str("the DOS IO tag is not a wind loads FEM input"),
+            Self::UnknownUnit(unit) => write!(f, "unknown force or moment unit: {}", unit),
+            Self::Layout {
+                source,
+                width,
+                dofs_per_body,
+            } => write!(
+                f,
+                "{} sample length ({}) is not a multiple of the {} components per body",
+                source, width, dofs_per_body
+            ),
+            Self::BodyIndex {
+                source,
+                index,
+                n_body,
+            } => write!(
+                f,
+                "{} body index {} is out of range (number of bodies: {})",
+                source, index, n_body
+            ),
+            Self::MissingSource(source) => write!(f, "{} loads not present", source),
+            Self::DuplicateSource(source) => write!(f, "{} is given more than once", source),
+            Self::AlreadySelected(source) => write!(
+                f,
+                "{} loads have already been selected under another FEM input",
+                source
+            ),
+            Self::UnorderedSource(source) => {
+                write!(
+                    f,
+                    "{} loads are selected but missing from the order",
+                    source
+                )
+            }
+            Self::Mismatch {
+                expected,
+                got,
+                source,
+            } => write!(f, "{} has {} samples instead of {}", source, got, expected),
+            Self::NonFinite {
+                source,
+                index,
+                component,
+            } => write!(
+                f,
+                "{} sample #{} has a non-finite value in component #{}",
+                source, index, component
+            ),
+            Self::BodyCount {
+                source,
+                n_body,
+                n_position,
+            } => write!(
+                f,
+                "{} has {} bodies but {} body positions or orientations are given",
+                source, n_body, n_position
+            ),
+            Self::SourceBodies {
+                source,
+                expected,
+                got,
+            } => write!(f, "{} has {} bodies instead of {}", source, got, expected),
+            Self::IndexRange {
+                min_index,
+                max_index,
+                n_sample,
+            } => write!(
+                f,
+                "invalid sample index range [{},{}[ (number of samples: {})",
+                min_index, max_index, n_sample
+            ),
+            Self::Width {
+                source,
+                expected,
+                got,
+            } => write!(
+                f,
+                "{} expects {} components but {} are given",
+                source, expected, got
+            ),
+            Self::Reshape {
+                source,
+                len,
+                dofs_per_sample,
+            } => write!(
+                f,
+                "{} loads of length {} cannot be reshaped into samples of {} components",
+                source, len, dofs_per_sample
+            ),
+            Self::Component {
+                source,
+                component,
+                width,
+            } => write!(
+                f,
+                "{} has no component #{} (sample length: {})",
+                source, component, width
+            ),
+            Self::Nfft { nfft, n_sample } => write!(
+                f,
+                "the FFT length {} must be between 2 and the number of samples ({})",
+                nfft, n_sample
+            ),
+            Self::NonUniformTime {
+                index,
+                step,
+                expected,
+            } => write!(
+                f,
+                "non-uniform time vector: time step #{} is {}s instead of {}s",
+                index, step, expected
+            ),
             Self::FileNotFound(e) => write!(f, "wind loads data file not found: {}", e),
+            Self::FileAccess { path, source } => {
+                write!(f, "cannot access wind loads file {}: {}", path, source)
+            }
             Self::PickleRead(e) => write!(f, "cannot read wind loads data file: {}", e),
-            Self::Outputs => f.write_str(""),
+            Self::Decompression(e) => write!(f, "cannot decompress wind loads data file: {}", e),
+            Self::StreamRead(e) => write!(f, "cannot read wind loads stream file: {}", e),
+            Self::JsonRead(e) => write!(f, "cannot read wind loads JSON file: {}", e),
+            Self::Outputs => f.write_str("failed to produce wind load outputs"),
             Self::Inputs => f.write_str("WindLoading takes no inputs"),
         }
     }
 }
 impl From<std::io::Error> for WindLoadsError {
     fn from(e: std::io::Error) -> Self {
-        Self::FileNotFound(e)
+        Self::FileNotFound(Arc::new(e))
     }
 }
 impl From<serde_pickle::Error> for WindLoadsError {
     fn from(e: serde_pickle::Error) -> Self {
-        Self::PickleRead(e)
+        Self::PickleRead(Arc::new(e))
+    }
+}
+impl From<serde_json::Error> for WindLoadsError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::JsonRead(Arc::new(e))
     }
 }
 impl std::error::Error for WindLoadsError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::FileNotFound(source) => Some(source),
+            Self::FileAccess { source, .. } => Some(source),
             Self::PickleRead(source) => Some(source),
+            Self::Decompression(source) => Some(source),
+            Self::StreamRead(source) => Some(source),
+            Self::JsonRead(source) => Some(source),
             _ => None,
         }
     }
 }
 
 type Result<T> = std::result::Result<T, WindLoadsError>;
+
+/// Returns the error of a wind loads `source` absent from the loads, whether it has been `selected` or not
+fn missing_source(selected: &[(&str, usize)], source: &str) -> WindLoadsError {
+    if selected.iter().any(|(x, _)| *x == source) {
+        WindLoadsError::AlreadySelected(source.to_string())
+    } else {
+        WindLoadsError::MissingSource(source.to_string())
+    }
+}
+
+/// Opens the file at `path`, the error keeps track of the path
+fn open_file<P: AsRef<Path>>(path: P) -> Result<File> {
+    let path = path.as_ref();
+    File::open(path).map_err(|e| WindLoadsError::FileAccess {
+        path: path.display().to_string(),
+        source: Arc::new(e),
+    })
+}
+/// Creates the file at `path`, the error keeps track of the path
+fn create_file<P: AsRef<Path>>(path: P) -> Result<File> {
+    let path = path.as_ref();
+    File::create(path).map_err(|e| WindLoadsError::FileAccess {
+        path: path.display().to_string(),
+        source: Arc::new(e),
+    })
+}
+/// Reader calling `on_progress` with the number of bytes read and `total`
+struct ProgressReader<R, F> {
+    inner: R,
+    read: u64,
+    reported: u64,
+    total: u64,
+    on_progress: F,
+}
+impl<R: io::Read, F: FnMut(u64, u64)> io::Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read += n as u64;
+        if (n == 0 && self.reported < self.read) || self.read - self.reported >= PROGRESS_INTERVAL {
+            self.reported = self.read;
+            (self.on_progress)(self.read, self.total);
+        }
+        Ok(n)
+    }
+}
 type Outputs = Option<std::vec::IntoIter<Vec<f64>>>;
+/// Selected loads with the name of their wind loads source, `None` for merged sources
+type TaggedLoads = (Option<&'static str>, IO<std::vec::IntoIter<Vec<f64>>>);
+
+/// Default capacity of the pickle file reader buffer (8MiB)
+const PICKLE_READER_CAPACITY: usize = 8 * 1024 * 1024;
+/// Gzip file header magic bytes
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+/// Number of bytes read between 2 progress reports of [`WindLoads::from_pickle_with_progress`] (1MiB)
+pub const PROGRESS_INTERVAL: u64 = 1024 * 1024;
+/// Relative tolerance on the uniformity of the time steps
+pub const TIME_STEP_TOLERANCE: f64 = 1e-6;
+/// Half length of the window of the local median used to detect spikes
+const DESPIKE_HALF_WINDOW: usize = 3;
+/// Orientations `[azimuth, tilt]` (degrees) of the M1 segments #1 to #7 in the OSS frame
+///
+/// The local frame of a segment is the OSS frame rotated by the azimuth around the z axis and
+/// then tilted toward the optical axis around the rotated y axis, the center segment #7 is not tilted
+pub const M1_SEGMENT_ORIENTATIONS: [[f64; 2]; 7] = [
+    [90., 13.522],
+    [30., 13.522],
+    [-30., 13.522],
+    [-90., 13.522],
+    [-150., 13.522],
+    [150., 13.522],
+    [0., 0.],
+];
+/// Returns the rotation matrix from the local frame of a M1 segment with orientation `[azimuth, tilt]` to the OSS frame
+fn m1_segment_rotation([azimuth, tilt]: [f64; 2]) -> [[f64; 3]; 3] {
+    let (sa, ca) = azimuth.to_radians().sin_cos();
+    let (st, ct) = (-tilt).to_radians().sin_cos();
+    // Rz(azimuth) * Ry(-tilt)
+    [
+        [ca * ct, -sa, ca * st],
+        [sa * ct, ca, sa * st],
+        [-st, 0., ct],
+    ]
+}
+/// Returns true if all the `samples` have the same length
+fn is_rectangular(samples: &[Vec<f64>]) -> bool {
+    samples.windows(2).all(|x| x[0].len() == x[1].len())
+}
+/// Rotates the 3 components vector `v` with the rotation matrix `rotation`: `v = R * v`
+fn rotate_vector(rotation: &[[f64; 3]; 3], v: &mut [f64]) {
+    let u = [v[0], v[1], v[2]];
+    v.iter_mut()
+        .zip(rotation)
+        .for_each(|(v, r)| *v = r.iter().zip(&u).map(|(r, u)| r * u).sum());
+}
+/// Wind loads sources in the order of [`WindLoads::select_all`]
+const SELECT_ALL: [&str; 7] = [
+    "OSS_TopEnd_6F",
+    "MC_M2_lcl_force_6F",
+    "OSS_Truss_6F",
+    "OSS_M1_lcl_6F",
+    "OSS_Cell_lcl_6F",
+    "OSS_GIR_6F",
+    "OSS_CRING_6F",
+];
 
 macro_rules! loads {
-    ($($name:expr, $variant:ident),+) => {
+    ($($name:literal $(| $alias:literal)*, $variant:ident),+) => {
         /// Wind loads forces and moments
         ///
         /// A time vector containing vectors of forces and moments
         #[derive(Deserialize, Debug,Clone)]
         pub enum Loads {
-            $(#[serde(rename = $name)]
+            $(#[serde(rename = $name $(, alias = $alias)*)]
               $variant(Vec<Vec<f64>>)),+
         }
+        /// Returns the names of the wind loads sources
+        pub fn wind_loads_name() -> Vec<&'static str> {
+            vec![$($name),+]
+        }
+        /// Returns the wind loads source name matching `name` or one of its alternate names
+        fn source_name(name: &str) -> Option<&'static str> {
+            match name {
+                $($name $(| $alias)* => Some($name),)+
+                _ => None,
+            }
+        }
+        /// Returns the wind loads of the source `name` with the time series `io`
+        fn loads_from_name(name: &str, io: Vec<Vec<f64>>) -> Option<Loads> {
+            match name {
+                $($name => Some(Loads::$variant(io)),)+
+                _ => None,
+            }
+        }
+        /// Returns the name of the wind loads source matching the DOS `IO`
+        fn io_source_name<T>(io: &IO<T>) -> Option<&'static str> {
+            match io {
+                $(IO::$variant { .. } => Some($name),)+
+                _ => None,
+            }
+        }
+        /// Returns the DOS `IO` matching the wind loads source `name` with `data`
+        fn io_from_name<T>(name: &str, data: T) -> Option<IO<T>> {
+            match name {
+                $($name => Some(IO::$variant { data: Some(data) }),)+
+                _ => None,
+            }
+        }
         impl Loads {
+            /// Returns the name of the wind loads source
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Loads::$variant(_) => $name),+
+                }
+            }
             /// Returns the number of samples in the time series
             pub fn len(&self) -> usize {
                 match self {
                     $(Loads::$variant(io) => io.len()),+
                 }
             }
+            /// Returns true if the time series is empty
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+            fn as_slice(&self) -> &[Vec<f64>] {
+                match self {
+                    $(Loads::$variant(io) => io),+
+                }
+            }
+            /// Returns a wind loads of the same source with the time series `io`
+            fn with_io(&self, io: Vec<Vec<f64>>) -> Self {
+                match self {
+                    $(Loads::$variant(_) => Loads::$variant(io)),+
+                }
+            }
             /// Return the loads
             pub fn io(self) -> Vec<Vec<f64>> {
                 match self {
                     $(Loads::$variant(io) => io),+
                 }
             }
+            /// Returns a reference to the loads
+            pub fn io_ref(&self) -> &Vec<Vec<f64>> {
+                match self {
+                    $(Loads::$variant(io) => io),+
+                }
+            }
+            /// Returns a mutable reference to the loads
+            pub fn io_mut(&mut self) -> &mut Vec<Vec<f64>> {
+                match self {
+                    $(Loads::$variant(io) => io),+
+                }
+            }
+            /// Decimates the time series keeping one sample every `decimation_rate` samples
+            ///
+            /// The samples are taken as is, meaning that any content above the new Nyquist frequency
+            /// is aliased into the lower frequencies, see [`decimate_filtered`](Loads::decimate_filtered)
             pub fn decimate(&mut self, decimation_rate: usize) {
                 match self {
                     $(Loads::$variant(io) => {
@@ -109,6 +526,16 @@ macro_rules! loads {
                 }
             }
         }
+        /// Serializes the loads as a single entry map `{name: loads}`
+        impl Serialize for Loads {
+            fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                let mut map = serializer.serialize_map(Some(1))?;
+                match self {
+                    $(Loads::$variant(io) => map.serialize_entry($name, io)?),+
+                }
+                map.end()
+            }
+        }
     };
 }
 loads!(
@@ -124,13 +551,630 @@ loads!(
     OSSCellLcl6F,
     "OSS_M1_lcl_6F",
     OSSM1Lcl6F,
-    "MC_M2_lcl_force_6F",
+    "MC_M2_lcl_force_6F" | "MC_M2_lcl_6F",
     MCM2Lcl6F
 );
+/// Wind loads statistics
+///
+/// Each field holds the statistic of each component of the loads over time
+#[derive(Debug, Clone, Default)]
+pub struct LoadStats {
+    pub mean: Vec<f64>,
+    pub std: Vec<f64>,
+    pub rms: Vec<f64>,
+    pub min: Vec<f64>,
+    pub max: Vec<f64>,
+}
+
+/// Force and moment applied to a body
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Wrench {
+    pub force: [f64; 3],
+    pub moment: [f64; 3],
+}
+impl TryFrom<&[f64]> for Wrench {
+    type Error = WindLoadsError;
+    /// Converts the 6 components `[Fx,Fy,Fz,Mx,My,Mz]` of a body into a wrench
+    fn try_from(x: &[f64]) -> Result<Self> {
+        match x {
+            &[fx, fy, fz, mx, my, mz] => Ok(Self {
+                force: [fx, fy, fz],
+                moment: [mx, my, mz],
+            }),
+            _ => Err(WindLoadsError::Width {
+                source: "wrench".to_string(),
+                expected: 6,
+                got: x.len(),
+            }),
+        }
+    }
+}
+
+impl Loads {
+    /// Creates the wind loads of the source `name` from the concatenation of `dofs_per_sample` long samples
+    ///
+    /// `name` is one of the names returned by [`wind_loads_name`]
+    pub fn from_flat(name: &str, flat: Vec<f64>, dofs_per_sample: usize) -> Result<Loads> {
+        assert!(
+            dofs_per_sample > 0,
+            "dofs_per_sample must be greater than 0"
+        );
+        let name =
+            source_name(name).ok_or_else(|| WindLoadsError::UnknownSource(name.to_string()))?;
+        let samples = flat.chunks_exact(dofs_per_sample);
+        if !samples.remainder().is_empty() {
+            return Err(WindLoadsError::Reshape {
+                source: name.to_string(),
+                len: flat.len(),
+                dofs_per_sample,
+            });
+        }
+        let io = samples.map(|x| x.to_vec()).collect();
+        loads_from_name(name, io).ok_or_else(|| WindLoadsError::UnknownSource(name.to_string()))
+    }
+    /// Returns the mean, standard deviation, RMS, minimum and maximum of each component over time
+    pub fn stats(&self) -> LoadStats {
+        let io = self.as_slice();
+        let n = io.len() as f64;
+        let width = io.first().map_or(0, |x| x.len());
+        let mut stats = LoadStats {
+            mean: vec![0f64; width],
+            std: vec![0f64; width],
+            rms: vec![0f64; width],
+            min: vec![f64::INFINITY; width],
+            max: vec![f64::NEG_INFINITY; width],
+        };
+        for x in io {
+            for (k, x) in x.iter().enumerate() {
+                stats.mean[k] += x / n;
+                stats.rms[k] += x * x / n;
+                stats.min[k] = stats.min[k].min(*x);
+                stats.max[k] = stats.max[k].max(*x);
+            }
+        }
+        stats.std = stats
+            .rms
+            .iter()
+            .zip(&stats.mean)
+            .map(|(ms, m)| (ms - m * m).max(0f64).sqrt())
+            .collect();
+        stats.rms.iter_mut().for_each(|x| *x = x.sqrt());
+        stats
+    }
+    /// Returns the time series of the component `component` of the loads
+    pub fn component_series(&self, component: usize) -> Vec<f64> {
+        self.as_slice().iter().map(|x| x[component]).collect()
+    }
+    /// Returns the time series of the component `component` of the loads or an error if a sample has no such component
+    fn checked_component_series(&self, component: usize) -> Result<Vec<f64>> {
+        self.as_slice()
+            .iter()
+            .map(|x| x.get(component).copied())
+            .collect::<Option<Vec<f64>>>()
+            .ok_or_else(|| WindLoadsError::Component {
+                source: self.name().to_string(),
+                component,
+                width: self.as_slice().first().map_or(0, |x| x.len()),
+            })
+    }
+    /// Returns an iterator over the time series of each component of the loads
+    pub fn components(&self) -> impl Iterator<Item = Vec<f64>> + '_ {
+        let width = self.as_slice().first().map_or(0, |x| x.len());
+        (0..width).map(move |k| self.component_series(k))
+    }
+    /// Power spectral density of the component `component` of the loads
+    ///
+    /// The power spectral density is estimated with Welch's method using segments of `nfft` samples
+    /// with 50% overlap and a Hann window, `nfft` must be between 2 and the number of samples.
+    /// Returns the pairs (frequency, power) from 0 to the Nyquist frequency
+    pub fn psd(&self, component: usize, sampling_hz: f64, nfft: usize) -> Result<Vec<(f64, f64)>> {
+        let x = self.checked_component_series(component)?;
+        if nfft < 2 || nfft > x.len() {
+            return Err(WindLoadsError::Nfft {
+                nfft,
+                n_sample: x.len(),
+            });
+        }
+        Ok(signal::welch_csd(&x, &x, sampling_hz, nfft)
+            .into_iter()
+            .map(|(f, p)| (f, p.re))
+            .collect())
+    }
+    /// Returns the forces of all the bodies
+    ///
+    /// Each sample is assumed to be made of the forces `[Fx,Fy,Fz]` followed by the moments `[Mx,My,Mz]`
+    /// of each body, the forces of all the bodies are concatenated for each time sample
+    pub fn forces(&self) -> Result<Vec<Vec<f64>>> {
+        self.body_components(0..3)
+    }
+    /// Returns the moments of all the bodies
+    ///
+    /// Each sample is assumed to be made of the forces `[Fx,Fy,Fz]` followed by the moments `[Mx,My,Mz]`
+    /// of each body, the moments of all the bodies are concatenated for each time sample
+    pub fn moments(&self) -> Result<Vec<Vec<f64>>> {
+        self.body_components(3..6)
+    }
+    /// Returns the wrench of each body for each time sample
+    ///
+    /// Each sample is assumed to be made of the forces `[Fx,Fy,Fz]` followed by the moments `[Mx,My,Mz]` of each body
+    pub fn wrenches(&self) -> Result<Vec<Vec<Wrench>>> {
+        self.check_layout_with(6)?;
+        self.as_slice()
+            .iter()
+            .map(|x| x.chunks(6).map(Wrench::try_from).collect())
+            .collect()
+    }
+    /// Returns the components within `range` of the 6 components of each body
+    fn body_components(&self, range: std::ops::Range<usize>) -> Result<Vec<Vec<f64>>> {
+        self.check_layout_with(6)?;
+        Ok(self
+            .as_slice()
+            .iter()
+            .map(|x| {
+                x.chunks(6)
+                    .flat_map(|x| x[range.clone()].iter().cloned())
+                    .collect()
+            })
+            .collect())
+    }
+    /// Returns true if all the time samples have the same length
+    pub fn is_rectangular(&self) -> bool {
+        is_rectangular(self.as_slice())
+    }
+    /// Returns the number of bodies of the wind loads source in the telescope FEM
+    pub fn n_body(&self) -> usize {
+        match self {
+            Loads::OSSTopEnd6F(_) | Loads::OSSGIR6F(_) => 1,
+            Loads::OSSCRING6F(_) => 2,
+            Loads::OSSTruss6F(_) => 3,
+            Loads::OSSCellLcl6F(_) | Loads::OSSM1Lcl6F(_) | Loads::MCM2Lcl6F(_) => 7,
+        }
+    }
+    /// Returns the number of bodies and the number of components per body of the samples
+    ///
+    /// Each sample is assumed to be made of the forces `[Fx,Fy,Fz]` followed by the moments `[Mx,My,Mz]` of each body,
+    /// the number of bodies is inferred from the sample length that must be the same for all samples and a multiple of 6,
+    /// and is checked against the number of bodies of the source, see [`n_body`](Loads::n_body).
+    /// The layout of the loads no longer matches the source after [`select_bodies`](Loads::select_bodies)
+    pub fn layout(&self) -> Result<(usize, usize)> {
+        let n_body = self.check_layout_with(6)?;
+        if !self.is_empty() && n_body != self.n_body() {
+            return Err(WindLoadsError::SourceBodies {
+                source: self.name().to_string(),
+                expected: self.n_body(),
+                got: n_body,
+            });
+        }
+        Ok((n_body, 6))
+    }
+    /// Checks that each sample is made of `dofs_per_body` components per body and returns the number of bodies
+    fn check_layout_with(&self, dofs_per_body: usize) -> Result<usize> {
+        let io = self.as_slice();
+        let width = io.first().map_or(0, |x| x.len());
+        match io
+            .iter()
+            .find(|x| x.len() != width || x.len() % dofs_per_body != 0)
+        {
+            Some(x) => Err(WindLoadsError::Layout {
+                source: self.name().to_string(),
+                width: x.len(),
+                dofs_per_body,
+            }),
+            None => Ok(width / dofs_per_body),
+        }
+    }
+    /// Keeps only the bodies `body_indices` in each sample
+    ///
+    /// Each sample is assumed to be made of `dofs_per_body` components per body,
+    /// the bodies are kept in the order of `body_indices`
+    pub fn select_bodies(&mut self, body_indices: &[usize], dofs_per_body: usize) -> Result<()> {
+        assert!(dofs_per_body > 0, "dofs_per_body must be greater than 0");
+        let n_body = self.check_layout_with(dofs_per_body)?;
+        if let Some(&index) = body_indices.iter().find(|&&i| i >= n_body) {
+            return Err(WindLoadsError::BodyIndex {
+                source: self.name().to_string(),
+                index,
+                n_body,
+            });
+        }
+        self.io_mut().iter_mut().for_each(|x| {
+            *x = body_indices
+                .iter()
+                .flat_map(|i| {
+                    x[i * dofs_per_body..(i + 1) * dofs_per_body]
+                        .iter()
+                        .cloned()
+                })
+                .collect();
+        });
+        Ok(())
+    }
+    /// Returns the time series of the magnitude of the force on the body `body`
+    pub fn force_magnitude(&self, body: usize) -> Result<Vec<f64>> {
+        self.magnitude(body, 0)
+    }
+    /// Returns the time series of the magnitude of the moment on the body `body`
+    pub fn moment_magnitude(&self, body: usize) -> Result<Vec<f64>> {
+        self.magnitude(body, 3)
+    }
+    /// Returns the time series of the norm of the 3 components of body `body` starting at component `offset`
+    fn magnitude(&self, body: usize, offset: usize) -> Result<Vec<f64>> {
+        let n_body = self.check_layout_with(6)?;
+        if body >= n_body {
+            return Err(WindLoadsError::BodyIndex {
+                source: self.name().to_string(),
+                index: body,
+                n_body,
+            });
+        }
+        let i = 6 * body + offset;
+        Ok(self
+            .as_slice()
+            .iter()
+            .map(|x| x[i..i + 3].iter().map(|x| x * x).sum::<f64>().sqrt())
+            .collect())
+    }
+    /// Transports the moments of each body by the moment arm `arm`
+    ///
+    /// The moments are recomputed as `M + arm x F`
+    pub fn translate_moments(&mut self, arm: [f64; 3]) -> Result<()> {
+        self.check_layout_with(6)?;
+        let [rx, ry, rz] = arm;
+        self.io_mut()
+            .iter_mut()
+            .flat_map(|x| x.chunks_mut(6))
+            .for_each(|x| {
+                let (fx, fy, fz) = (x[0], x[1], x[2]);
+                x[3] += ry * fz - rz * fy;
+                x[4] += rz * fx - rx * fz;
+                x[5] += rx * fy - ry * fx;
+            });
+        Ok(())
+    }
+    /// Returns the time series of the net forces and moments of all the bodies about `point`
+    ///
+    /// The net moment is the sum over all bodies of `M_i + (r_i - point) x F_i`
+    /// where `r_i` is the position of body `i` in `body_positions`
+    pub fn resultant_at(
+        &self,
+        point: [f64; 3],
+        body_positions: &[[f64; 3]],
+    ) -> Result<Vec<[f64; 6]>> {
+        let n_body = self.check_layout_with(6)?;
+        if n_body != body_positions.len() {
+            return Err(WindLoadsError::BodyCount {
+                source: self.name().to_string(),
+                n_body,
+                n_position: body_positions.len(),
+            });
+        }
+        let arms: Vec<[f64; 3]> = body_positions
+            .iter()
+            .map(|r| [r[0] - point[0], r[1] - point[1], r[2] - point[2]])
+            .collect();
+        Ok(self
+            .as_slice()
+            .iter()
+            .map(|x| {
+                x.chunks(6)
+                    .zip(&arms)
+                    .fold([0f64; 6], |mut w, (x, [rx, ry, rz])| {
+                        let (fx, fy, fz) = (x[0], x[1], x[2]);
+                        w[0] += fx;
+                        w[1] += fy;
+                        w[2] += fz;
+                        w[3] += x[3] + ry * fz - rz * fy;
+                        w[4] += x[4] + rz * fx - rx * fz;
+                        w[5] += x[5] + rx * fy - ry * fx;
+                        w
+                    })
+            })
+            .collect())
+    }
+    /// Rotates the forces and the moments of each body with the rotation matrix `rotation`
+    ///
+    /// Both the forces and the moments are transformed as `R * v`
+    pub fn rotate(&mut self, rotation: [[f64; 3]; 3]) -> Result<()> {
+        self.check_layout_with(6)?;
+        self.io_mut()
+            .iter_mut()
+            .flat_map(|x| x.chunks_mut(3))
+            .for_each(|v| rotate_vector(&rotation, v));
+        Ok(())
+    }
+    /// Rotates the forces and the moments of each body `i` with the rotation matrix `rotations[i]`
+    ///
+    /// Both the forces and the moments are transformed as `R_i * v`
+    pub fn rotate_bodies(&mut self, rotations: &[[[f64; 3]; 3]]) -> Result<()> {
+        let n_body = self.check_layout_with(6)?;
+        if n_body != rotations.len() {
+            return Err(WindLoadsError::BodyCount {
+                source: self.name().to_string(),
+                n_body,
+                n_position: rotations.len(),
+            });
+        }
+        for sample in self.io_mut() {
+            for (wrench, rotation) in sample.chunks_mut(6).zip(rotations) {
+                wrench
+                    .chunks_mut(3)
+                    .for_each(|v| rotate_vector(rotation, v));
+            }
+        }
+        Ok(())
+    }
+    /// Pads the time series with zeros up to `n_samples` samples
+    pub fn pad_to(&mut self, n_samples: usize) {
+        let io = self.io_mut();
+        let width = io.first().map_or(0, |x| x.len());
+        if io.len() < n_samples {
+            io.resize(n_samples, vec![0f64; width]);
+        }
+    }
+    /// Multiplies every component of the loads by `gain`
+    pub fn scale(&mut self, gain: f64) {
+        self.io_mut()
+            .iter_mut()
+            .for_each(|x| x.iter_mut().for_each(|x| *x *= gain));
+    }
+    /// Adds the constant `offset` to every time sample of the loads
+    ///
+    /// `offset` must have as many components as the samples
+    pub fn add_offset(&mut self, offset: &[f64]) -> Result<()> {
+        let width = self.as_slice().first().map_or(0, |x| x.len());
+        if offset.len() != width {
+            return Err(WindLoadsError::Width {
+                source: self.name().to_string(),
+                expected: width,
+                got: offset.len(),
+            });
+        }
+        self.io_mut()
+            .iter_mut()
+            .for_each(|x| x.iter_mut().zip(offset).for_each(|(x, o)| *x += o));
+        Ok(())
+    }
+    /// Zeroes the components of each body that are masked out by `dof_mask`
+    ///
+    /// `dof_mask` has one entry per component `[Fx,Fy,Fz,Mx,My,Mz]` of a body, the components set to `false` are zeroed
+    pub fn keep_dofs(&mut self, dof_mask: &[bool]) -> Result<()> {
+        let dofs_per_body = 6;
+        self.check_layout_with(dofs_per_body)?;
+        if dof_mask.len() != dofs_per_body {
+            return Err(WindLoadsError::Width {
+                source: self.name().to_string(),
+                expected: dofs_per_body,
+                got: dof_mask.len(),
+            });
+        }
+        self.io_mut()
+            .iter_mut()
+            .flat_map(|x| x.chunks_mut(dofs_per_body))
+            .flat_map(|x| x.iter_mut().zip(dof_mask))
+            .filter(|(_, keep)| !**keep)
+            .for_each(|(x, _)| *x = 0.);
+        Ok(())
+    }
+    /// Cumulative trapezoidal integration of each component of the time series with the time step `dt`
+    ///
+    /// Returns the running integrals (impulse and angular impulse) starting at 0, one per time sample
+    pub fn integrate(&self, dt: f64) -> Vec<Vec<f64>> {
+        let io = self.as_slice();
+        let width = io.first().map_or(0, |x| x.len());
+        let mut integral = vec![0f64; width];
+        let mut running: Vec<Vec<f64>> = Vec::with_capacity(io.len());
+        running.extend(io.first().map(|_| integral.clone()));
+        for x in io.windows(2) {
+            integral
+                .iter_mut()
+                .zip(x[0].iter().zip(&x[1]))
+                .for_each(|(i, (a, b))| *i += 0.5 * dt * (a + b));
+            running.push(integral.clone());
+        }
+        running
+    }
+    /// Returns the trapezoidal integral of each component over the whole time series, see [`integrate`](Loads::integrate)
+    pub fn final_integral(&self, dt: f64) -> Vec<f64> {
+        let io = self.as_slice();
+        let width = io.first().map_or(0, |x| x.len());
+        io.windows(2).fold(vec![0f64; width], |mut integral, x| {
+            integral
+                .iter_mut()
+                .zip(x[0].iter().zip(&x[1]))
+                .for_each(|(i, (a, b))| *i += 0.5 * dt * (a + b));
+            integral
+        })
+    }
+    /// Tapers the first and last `n` samples of the time series to zero with a raised cosine
+    ///
+    /// `n` is limited to half the number of samples
+    pub fn taper_edges(&mut self, n: usize) {
+        let io = self.io_mut();
+        let n = n.min(io.len() / 2);
+        let len = io.len();
+        for i in 0..n {
+            let w = 0.5 * (1. - (std::f64::consts::PI * i as f64 / n as f64).cos());
+            io[i].iter_mut().for_each(|x| *x *= w);
+            io[len - 1 - i].iter_mut().for_each(|x| *x *= w);
+        }
+    }
+    /// Saturates every component of the loads to the range from `min` to `max`
+    pub fn clamp(&mut self, min: f64, max: f64) {
+        assert!(min <= max, "min must be less than or equal to max");
+        self.io_mut()
+            .iter_mut()
+            .for_each(|x| x.iter_mut().for_each(|x| *x = x.clamp(min, max)));
+    }
+    /// Replaces the spikes in each component of the time series
+    ///
+    /// A sample is a spike if it departs from the local median of the 7 samples centered on it
+    /// by more than `n_sigma` standard deviations of the component;
+    /// spikes are replaced by the linear interpolation of the nearest valid samples
+    pub fn despike(&mut self, n_sigma: f64) {
+        signal::map_components(self.io_mut(), |x| {
+            signal::despike(x, n_sigma, DESPIKE_HALF_WINDOW)
+        });
+    }
+    /// Low-pass filters and decimates the time series keeping one sample every `decimation_rate` samples
+    ///
+    /// Each component is filtered with a zero-phase Hamming windowed FIR filter with
+    /// a cutoff at the new Nyquist frequency before decimation, preventing the aliasing of
+    /// the high frequency content that occurs with [`decimate`](Loads::decimate)
+    pub fn decimate_filtered(&mut self, decimation_rate: usize) {
+        if decimation_rate > 1 {
+            let taps =
+                signal::fir_lowpass_taps(0.5 / decimation_rate as f64, 20 * decimation_rate + 1);
+            signal::map_components(self.io_mut(), |x| signal::fir_filter_centered(x, &taps));
+        }
+        self.decimate(decimation_rate);
+    }
+    /// Downsamples the time series replacing each block of `factor` consecutive samples by their average
+    ///
+    /// The last block is averaged over the remaining samples if the number of samples is not a multiple of `factor`,
+    /// the number of samples is the same than with [`decimate`](Loads::decimate)
+    pub fn decimate_mean(&mut self, factor: usize) {
+        assert!(factor > 0, "the decimation factor must be greater than 0");
+        let io = self.io_mut();
+        let averaged: Vec<Vec<f64>> = io
+            .chunks(factor)
+            .map(|block| {
+                let n = block.len() as f64;
+                let width = block[0].len();
+                block.iter().fold(vec![0f64; width], |mut m, x| {
+                    m.iter_mut().zip(x).for_each(|(m, x)| *m += x / n);
+                    m
+                })
+            })
+            .collect();
+        *io = averaged;
+    }
+    /// Downsamples the time series keeping, in each block of `factor` consecutive samples, the sample with the largest force
+    ///
+    /// The force of a sample is the largest magnitude of the forces `[Fx,Fy,Fz]` of its bodies,
+    /// the number of samples is the same than with [`decimate`](Loads::decimate)
+    pub fn decimate_peak(&mut self, factor: usize) {
+        assert!(factor > 0, "the decimation factor must be greater than 0");
+        let force = |x: &[f64]| {
+            x.chunks(6)
+                .map(|x| x.iter().take(3).map(|x| x * x).sum::<f64>())
+                .fold(0f64, f64::max)
+        };
+        let io = self.io_mut();
+        let peaks: Vec<Vec<f64>> = io
+            .chunks(factor)
+            .map(|block| {
+                block
+                    .iter()
+                    .fold(
+                        &block[0],
+                        |peak, x| {
+                            if force(x) > force(peak) {
+                                x
+                            } else {
+                                peak
+                            }
+                        },
+                    )
+                    .clone()
+            })
+            .collect();
+        *io = peaks;
+    }
+    /// Low-pass filters each component of the time series with a Butterworth filter
+    ///
+    /// The filter is applied forward only and introduces a frequency dependent phase delay,
+    /// see [`lowpass_filtfilt`](Loads::lowpass_filtfilt) for a zero-phase filter
+    pub fn lowpass(&mut self, cutoff_hz: f64, sampling_hz: f64, order: usize) {
+        let sections = Self::butterworth(cutoff_hz, sampling_hz, order);
+        signal::map_components(self.io_mut(), |x| signal::sos_filter(&sections, x));
+    }
+    /// Zero-phase low-pass filtering of each component of the time series with a Butterworth filter
+    ///
+    /// The filter is applied forward and backward, doubling the filter order
+    pub fn lowpass_filtfilt(&mut self, cutoff_hz: f64, sampling_hz: f64, order: usize) {
+        let sections = Self::butterworth(cutoff_hz, sampling_hz, order);
+        signal::map_components(self.io_mut(), |x| signal::sos_filtfilt(&sections, x));
+    }
+    /// Band-pass filters each component of the time series keeping only the frequencies from `low_hz` to `high_hz`
+    ///
+    /// The frequencies outside the band are zeroed in the Fourier domain,
+    /// long time series are filtered by segments that are overlap-added.
+    /// As with any brick-wall filter, expect ringing at both ends of the time series
+    pub fn bandpass(&mut self, low_hz: f64, high_hz: f64, sampling_hz: f64) {
+        assert!(
+            low_hz >= 0f64 && low_hz < high_hz && high_hz <= 0.5 * sampling_hz,
+            "the band must be within 0 and the Nyquist frequency"
+        );
+        let (low, high) = (low_hz / sampling_hz, high_hz / sampling_hz);
+        signal::map_components(self.io_mut(), |x| signal::bandpass(x, low, high));
+    }
+    /// Smooths each component of the time series with a centered moving average over `window` samples
+    ///
+    /// `window` must be odd, the window is shortened at both ends of the time series
+    pub fn moving_average(&mut self, window: usize) {
+        assert!(window % 2 == 1, "the moving average window must be odd");
+        signal::map_components(self.io_mut(), |x| signal::moving_average(x, window));
+    }
+    /// Filters each component of the time series with a centered sliding median over `window` samples
+    ///
+    /// `window` must be odd, the window is shrunk symmetrically at both ends of the time series.
+    /// Unlike [`moving_average`](Loads::moving_average), isolated spikes are removed rather than smeared
+    pub fn median_filter(&mut self, window: usize) {
+        assert!(window % 2 == 1, "the median filter window must be odd");
+        signal::map_components(self.io_mut(), |x| signal::median_filter(x, window));
+    }
+    /// Returns the (min, max) envelope of the component `comp` of the loads over a centered sliding window of `window` samples
+    ///
+    /// `window` must be odd, the window is shortened at both ends of the time series
+    pub fn envelope(&self, window: usize, comp: usize) -> Result<Vec<(f64, f64)>> {
+        assert!(window % 2 == 1, "the envelope window must be odd");
+        Ok(signal::envelope(
+            &self.checked_component_series(comp)?,
+            window,
+        ))
+    }
+    /// Rainflow cycle counting of the component `comp` of the loads following the ASTM E1049 standard
+    ///
+    /// Returns the (range, mean, count) of each cycle, the count is 1 for full cycles and 0.5 for half cycles
+    pub fn rainflow(&self, comp: usize) -> Result<Vec<(f64, f64, f64)>> {
+        Ok(signal::rainflow(&self.checked_component_series(comp)?))
+    }
+    /// Removes the temporal mean of each component of the time series
+    ///
+    /// Returns the means that have been subtracted
+    pub fn detrend_mean(&mut self) -> Vec<f64> {
+        let io = self.io_mut();
+        let n = io.len() as f64;
+        let width = io.first().map_or(0, |x| x.len());
+        let means: Vec<f64> = io.iter().fold(vec![0f64; width], |mut m, x| {
+            m.iter_mut().zip(x).for_each(|(m, x)| *m += x / n);
+            m
+        });
+        io.iter_mut().for_each(|x| {
+            x.iter_mut().zip(&means).for_each(|(x, m)| *x -= m);
+        });
+        means
+    }
+    fn butterworth(cutoff_hz: f64, sampling_hz: f64, order: usize) -> Vec<signal::Biquad> {
+        assert!(order > 0, "the filter order must be greater than 0");
+        assert!(
+            cutoff_hz > 0f64 && cutoff_hz < 0.5 * sampling_hz,
+            "the cutoff frequency must be between 0 and the Nyquist frequency"
+        );
+        signal::butterworth_lowpass(order, cutoff_hz / sampling_hz)
+    }
+    /// Linearly interpolates the time series at the fractional sample `positions`
+    fn interpolate(&mut self, positions: &[f64]) {
+        let io = self.io_mut();
+        *io = signal::interpolate_samples(io, positions);
+    }
+}
 
 pub trait MatchWindLoads {
     fn data(&self, wind_loads: &Loads) -> Option<std::vec::IntoIter<Vec<f64>>>;
     fn ndata(&self, wind_loads: &Loads, n: usize) -> Option<std::vec::IntoIter<Vec<f64>>>;
+    fn matches(&self, wind_loads: &Loads) -> bool;
+    fn move_data(&self, wind_loads: Loads) -> Option<std::vec::IntoIter<Vec<f64>>>;
 }
 macro_rules! io_match_wind_loads {
     ($($variant:ident),+) => {
@@ -145,7 +1189,21 @@ macro_rules! io_match_wind_loads {
             /// Matches a wind loads to a DOS `IO` returning the wind load value as an iterator
             fn ndata(&self, wind_loads: &Loads, n: usize) -> Option<std::vec::IntoIter<Vec<f64>>> {
                 match (self,wind_loads) {
-                    $((IO::$variant{..}, Loads::$variant(v)) => Some(v[..n].to_owned().into_iter()),)+
+                    $((IO::$variant{..}, Loads::$variant(v)) => v.get(..n).map(|v| v.to_owned().into_iter()),)+
+                        (_, _) => None,
+                }
+            }
+            /// Returns true if a wind loads matches a DOS `IO`
+            fn matches(&self, wind_loads: &Loads) -> bool {
+                match (self,wind_loads) {
+                    $((IO::$variant{..}, Loads::$variant(_)) => true,)+
+                        (_, _) => false,
+                }
+            }
+            /// Matches a wind loads to a DOS `IO` returning the wind load value, moved into an iterator
+            fn move_data(&self, wind_loads: Loads) -> Option<std::vec::IntoIter<Vec<f64>>> {
+                match (self,wind_loads) {
+                    $((IO::$variant{..}, Loads::$variant(v)) => Some(v.into_iter()),)+
                         (_, _) => None,
                 }
             }
@@ -162,77 +1220,975 @@ io_match_wind_loads!(
     MCM2Lcl6F
 );
 
+macro_rules! io_wind_loads {
+    ($($variant:ident),+) => {
+        /// Returns the name of a DOS `IO` wind loads tag
+        fn io_name<T>(io: &IO<T>) -> &'static str {
+            match io {
+                $(IO::$variant{..} => stringify!($variant),)+
+                _ => "unknown",
+            }
+        }
+        /// Returns a DOS `IO` of the same kind than `tag` with `data`
+        fn io_retag<T, U>(tag: &IO<U>, data: Option<T>) -> Option<IO<T>> {
+            match tag {
+                $(IO::$variant{..} => Some(IO::$variant{ data }),)+
+                _ => None,
+            }
+        }
+        /// Returns a reference to the data of a DOS `IO` wind loads
+        fn io_data<T>(io: &IO<T>) -> Option<&T> {
+            match io {
+                $(IO::$variant{ data } => data.as_ref(),)+
+                _ => None,
+            }
+        }
+        /// Returns the number of samples left in a DOS `IO` wind loads
+        fn io_len(io: &IO<std::vec::IntoIter<Vec<f64>>>) -> Option<usize> {
+            io_data(io).map(|x| x.len())
+        }
+    };
+}
+io_wind_loads!(
+    OSSTopEnd6F,
+    OSSTruss6F,
+    OSSGIR6F,
+    OSSCRING6F,
+    OSSCellLcl6F,
+    OSSM1Lcl6F,
+    MCM2Lcl6F,
+    MCM2TE6F,
+    MCM2RB6F,
+    OSSM1Lcl,
+    MCM2Lcl
+);
+
 /// Wind loads builder
 ///
 /// This structure is used to read the forces and moments time series from a data file and to create the [`WindLoading`] structure
-#[derive(Deserialize)]
+///
+/// # Selection
+///
+/// The selectors move the loads of a source out of the data into the loads selected for the [`WindLoading`].
+/// A source that is selected again is fed with a copy of the loads selected before
+/// (e.g. [`topend`](WindLoads::topend) followed by [`m2_asm_topend`](WindLoads::m2_asm_topend)),
+/// unless it has been merged with another source, see [`merge_sources`](WindLoads::merge_sources),
+/// then the selection fails with [`WindLoadsError::AlreadySelected`].
+/// A selected source is put back into the data with [`deselect`](WindLoads::deselect)
+#[derive(Deserialize, Serialize)]
 pub struct WindLoads {
     /// forces and moments time series
     #[serde(rename = "outputs")]
     pub loads: Vec<Option<Loads>>,
     /// time vector
     pub time: Vec<f64>,
+    /// forces and moments units
+    #[serde(default)]
+    units: Units,
     #[serde(skip)]
     n_sample: Option<usize>,
     #[serde(skip)]
-    tagged_loads: Vec<IO<std::vec::IntoIter<Vec<f64>>>>,
+    tagged_loads: Vec<TaggedLoads>,
+    #[serde(skip)]
+    skipped_sources: Vec<String>,
+    // sources moved out of the loads with the index of their entry in the loads
+    #[serde(skip)]
+    selected_sources: Vec<(&'static str, usize)>,
+    #[serde(skip)]
+    checked: bool,
 }
 
 impl WindLoads {
+    /// Creates the wind loads from the `time` vector and the forces and moments time series
+    pub fn from_loads(time: Vec<f64>, loads: Vec<Option<Loads>>) -> Self {
+        Self {
+            loads,
+            time,
+            units: Units::default(),
+            n_sample: None,
+            tagged_loads: vec![],
+            skipped_sources: vec![],
+            selected_sources: vec![],
+            checked: false,
+        }
+    }
     /// Reads the wind loads from a pickle file
     pub fn from_pickle<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let f = File::open(path)?;
-        let r = BufReader::with_capacity(1_000_000_000, f);
-        let v: serde_pickle::Value = serde_pickle::from_reader(r)?;
+        Self::from_pickle_with_capacity(path, PICKLE_READER_CAPACITY)
+    }
+    /// Reads and concatenates the wind loads from several pickle files
+    ///
+    /// The files are concatenated in the order of their first time sample and
+    /// must all contain the same wind loads sources
+    pub fn from_pickles<P: AsRef<Path>>(paths: &[P]) -> Result<Self> {
+        let mut chunks = paths
+            .iter()
+            .map(Self::from_pickle)
+            .collect::<Result<Vec<_>>>()?;
+        chunks.sort_by(|a, b| {
+            let (ta, tb) = (a.time.first(), b.time.first());
+            ta.partial_cmp(&tb).unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let mut chunks = chunks.into_iter();
+        let mut wind_loads = chunks.next().ok_or(WindLoadsError::Empty)?;
+        for chunk in chunks {
+            let time = wind_loads.extend_loads(chunk)?;
+            wind_loads.time.extend(time);
+        }
+        Ok(wind_loads)
+    }
+    /// Reads the wind loads from a pickle file with a reader buffer of `capacity` bytes
+    ///
+    /// Gzip compressed pickle files are decompressed on the fly
+    pub fn from_pickle_with_capacity<P: AsRef<Path>>(path: P, capacity: usize) -> Result<Self> {
+        let f = open_file(path)?;
+        Self::from_pickle_reader(BufReader::with_capacity(capacity, f))
+    }
+    /// Reads the wind loads from a pickle file reporting the progress of the file reading
+    ///
+    /// `on_progress` is called with the number of bytes read and the file size
+    /// every [`PROGRESS_INTERVAL`] bytes and once the file has been entirely read
+    pub fn from_pickle_with_progress<P, F>(path: P, on_progress: F) -> Result<Self>
+    where
+        P: AsRef<Path>,
+        F: FnMut(u64, u64),
+    {
+        let f = open_file(path)?;
+        let total = f.metadata()?.len();
+        let r = ProgressReader {
+            inner: f,
+            read: 0,
+            reported: 0,
+            total,
+            on_progress,
+        };
+        Self::from_pickle_reader(BufReader::with_capacity(PICKLE_READER_CAPACITY, r))
+    }
+    /// Reads the wind loads from a pickle reader, gzip compressed data are decompressed on the fly
+    fn from_pickle_reader<R: BufRead>(mut r: R) -> Result<Self> {
+        let v: serde_pickle::Value = if r.fill_buf()?.starts_with(&GZIP_MAGIC) {
+            serde_pickle::from_reader(GzDecoder::new(r)).map_err(|e| match e {
+                serde_pickle::Error::Io(e) => WindLoadsError::Decompression(Arc::new(e)),
+                e => e.into(),
+            })?
+        } else {
+            serde_pickle::from_reader(r)?
+        };
+        Ok(pkl::from_value(v)?)
+    }
+    /// Reads the wind loads from a memory mapped pickle file
+    ///
+    /// The file is mapped into memory and deserialized from the mapped bytes,
+    /// avoiding the intermediate reader buffer
+    #[cfg(feature = "mmap")]
+    pub fn from_pickle_mmap<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let f = open_file(path)?;
+        // Safety: the file is only read and is expected not to be modified while mapped
+        let mmap = unsafe { memmap2::Mmap::map(&f)? };
+        let v: serde_pickle::Value = serde_pickle::from_slice(&mmap)?;
         Ok(pkl::from_value(v)?)
     }
+    /// Writes the wind loads to a pickle file
+    pub fn to_pickle<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let f = create_file(path)?;
+        let mut w = BufWriter::new(f);
+        pkl::to_writer(&mut w, self, true)?;
+        w.flush()?;
+        Ok(())
+    }
+    /// Writes the wind loads to a gzip compressed pickle file
+    pub fn to_pickle_gz<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let f = create_file(path)?;
+        let mut w = GzEncoder::new(BufWriter::new(f), Compression::default());
+        pkl::to_writer(&mut w, self, true)?;
+        w.finish()?.flush()?;
+        Ok(())
+    }
+    /// Returns the names of the wind loads sources present in the data
+    pub fn available_sources(&self) -> Vec<String> {
+        self.loads
+            .iter()
+            .filter_map(|x| x.as_ref().map(|x| x.name().to_string()))
+            .collect()
+    }
+    /// Returns a table of the wind loads sources with their number of samples and sample length,
+    /// followed by the time span of the time series
+    pub fn summary(&self) -> String {
+        let mut table = format!("{:<20} {:>10} {:>6}\n", "source", "samples", "width");
+        for loads in self.loads.iter().filter_map(|x| x.as_ref()) {
+            let width = loads.as_slice().first().map_or(0, |x| x.len());
+            table.push_str(&format!(
+                "{:<20} {:>10} {:>6}\n",
+                loads.name(),
+                loads.len(),
+                width
+            ));
+        }
+        match (self.time.first(), self.time.last()) {
+            (Some(t0), Some(t1)) => {
+                table.push_str(&format!("time: [{}, {}]s ({}s)", t0, t1, t1 - t0))
+            }
+            _ => table.push_str("time: none"),
+        }
+        table.push_str(&format!("\nunits: {}", self.units));
+        table
+    }
+    /// Writes the time series of the wind loads `source` to a CSV file
+    ///
+    /// The first column is the time and the following columns are the forces and moments
+    pub fn to_csv<P: AsRef<Path>>(&self, path: P, source: &str) -> Result<()> {
+        let loads = self
+            .loads
+            .iter()
+            .filter_map(|x| x.as_ref())
+            .find(|x| x.name() == source)
+            .ok_or_else(|| WindLoadsError::MissingSource(source.to_string()))?;
+        let data = loads.as_slice();
+        let width = data.first().map_or(0, |x| x.len());
+        if let Some(index) = data.iter().position(|x| x.len() != width) {
+            return Err(WindLoadsError::Jagged {
+                source: source.to_string(),
+                index,
+            });
+        }
+        let dofs = ["Fx", "Fy", "Fz", "Mx", "My", "Mz"];
+        let header: Vec<String> = (0..width)
+            .map(|i| match width {
+                6 => dofs[i].to_string(),
+                w if w % 6 == 0 => format!("{}_{}", dofs[i % 6], i / 6),
+                _ => format!("c_{}", i),
+            })
+            .collect();
+        let f = create_file(path)?;
+        let mut w = BufWriter::new(f);
+        writeln!(w, "time,{}", header.join(","))?;
+        for (t, sample) in self.time.iter().zip(data.iter()) {
+            let row: Vec<String> = sample.iter().map(|x| x.to_string()).collect();
+            writeln!(w, "{},{}", t, row.join(","))?;
+        }
+        w.flush()?;
+        Ok(())
+    }
+    /// Reads the wind loads from a JSON file
+    pub fn from_json<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let f = open_file(path)?;
+        let r = BufReader::new(f);
+        Ok(serde_json::from_reader(r)?)
+    }
+    /// Writes the wind loads to a JSON file
+    pub fn to_json<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let f = create_file(path)?;
+        let mut w = BufWriter::new(f);
+        serde_json::to_writer(&mut w, self)?;
+        w.flush()?;
+        Ok(())
+    }
+    /// Checks that the loads of all the wind loads sources, selected or not, are finite
+    ///
+    /// Returns an error with the source, the time index and the component of the first non-finite value
+    pub fn validate(&self) -> Result<()> {
+        let sources = self
+            .loads
+            .iter()
+            .filter_map(|x| x.as_ref().map(|x| (x.name(), x.as_slice())))
+            .chain(
+                self.tagged_loads
+                    .iter()
+                    .filter_map(|(_, x)| io_data(x).map(|data| (io_name(x), data.as_slice()))),
+            );
+        for (source, data) in sources {
+            for (index, sample) in data.iter().enumerate() {
+                if let Some(component) = sample.iter().position(|x| !x.is_finite()) {
+                    return Err(WindLoadsError::NonFinite {
+                        source: source.to_string(),
+                        index,
+                        component,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Checks that the loads are finite when building the [`WindLoading`], see [`validate`](WindLoads::validate)
+    pub fn checked(self) -> Self {
+        Self {
+            checked: true,
+            ..self
+        }
+    }
     /// Returns the number of samples in the time series
     fn len(&self) -> Result<usize> {
         self.loads
             .iter()
-            .find_map(|x| x.as_ref().and_then(|x| Some(x.len())))
+            .find_map(|x| x.as_ref().map(|x| x.len()))
+            .or_else(|| self.tagged_loads.iter().find_map(|(_, x)| io_len(x)))
             .ok_or(WindLoadsError::Len)
     }
-    pub fn range(mut self, t_min: f64, t_max: f64) -> Self {
-        let min_index = self.time.iter().position(|t| *t >= t_min).unwrap_or(0);
+    /// Returns the number of samples in the time series
+    pub fn n_samples(&self) -> Result<usize> {
+        self.len()
+    }
+    /// Returns true if there is no time series or if they are empty
+    pub fn is_empty(&self) -> bool {
+        self.len().map_or(true, |n| n == 0)
+    }
+    /// Returns the time vector
+    pub fn time(&self) -> &[f64] {
+        &self.time
+    }
+    /// Returns an iterator over the time and the samples of the wind loads source `name`
+    ///
+    /// The iterator stops at the end of the shortest of the time vector and of the time series
+    pub fn iter_source(&self, name: &str) -> Option<impl Iterator<Item = (f64, &Vec<f64>)>> {
+        self.loads
+            .iter()
+            .filter_map(|x| x.as_ref())
+            .find(|x| x.name() == name)
+            .map(|x| self.time.iter().cloned().zip(x.as_slice()))
+    }
+    /// Returns the time span of the time vector
+    pub fn duration(&self) -> Option<f64> {
+        match (self.time.first(), self.time.last()) {
+            (Some(t0), Some(t1)) => Some(t1 - t0),
+            _ => None,
+        }
+    }
+    /// Returns the number of simulation steps at `sampling_hz` covered by the time vector
+    ///
+    /// This is the number of samples after [`foh`](WindLoads::foh) interpolation at the same sampling frequency
+    pub fn n_step_for(&self, sampling_hz: usize) -> usize {
+        self.duration()
+            .map_or(0, |d| (d * sampling_hz as f64 + 1e-9).floor() as usize + 1)
+    }
+    /// Returns the sampling frequency estimated from the time vector
+    ///
+    /// The sampling frequency is the inverse of the median of the time steps,
+    /// it is `None` if there are less than 2 time samples or
+    /// if the time steps depart from the median by more than [`TIME_STEP_TOLERANCE`] (relative)
+    pub fn sampling_frequency(&self) -> Option<f64> {
+        self.assert_uniform_time(TIME_STEP_TOLERANCE).ok()?;
+        self.median_time_step().map(|dt| 1. / dt)
+    }
+    /// Returns the median of the time steps or `None` if there are less than 2 time samples
+    fn median_time_step(&self) -> Option<f64> {
+        let mut dt: Vec<f64> = self.time.windows(2).map(|t| t[1] - t[0]).collect();
+        if dt.is_empty() {
+            return None;
+        }
+        dt.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        Some(dt[dt.len() / 2])
+    }
+    /// Checks that the time steps are all equal to their median within the relative tolerance `tol`
+    ///
+    /// Returns an error with the index of the first irregular time step (the step between the time samples `index` and `index+1`),
+    /// a time vector with less than 2 time samples is uniform
+    pub fn assert_uniform_time(&self, tol: f64) -> Result<()> {
+        let Some(median) = self.median_time_step() else {
+            return Ok(());
+        };
+        match self
+            .time
+            .windows(2)
+            .map(|t| t[1] - t[0])
+            .enumerate()
+            .find(|(_, dt)| *dt <= 0f64 || (dt - median).abs() > tol * median)
+        {
+            Some((index, step)) => Err(WindLoadsError::NonUniformTime {
+                index,
+                step,
+                expected: median,
+            }),
+            None => Ok(()),
+        }
+    }
+    /// Returns the indices of the time samples bounding the time range [`t_min`,`t_max`[
+    ///
+    /// The indices are equal if there is no time sample within the time range
+    fn time_indices(&self, t_min: f64, t_max: f64) -> (usize, usize) {
+        let min_index = self
+            .time
+            .iter()
+            .position(|t| *t >= t_min)
+            .unwrap_or(self.time.len());
         let max_index = self
             .time
             .iter()
             .position(|t| *t >= t_max)
             .unwrap_or(self.time.len());
+        (min_index, max_index.max(min_index))
+    }
+    pub fn range(mut self, t_min: f64, t_max: f64) -> Self {
+        let (min_index, max_index) = self.time_indices(t_min, t_max);
+        self.slice(min_index, max_index);
+        self
+    }
+    /// Trims the loads and the time vector to the samples with indices in [`min_index`,`max_index`[
+    pub fn range_indices(mut self, min_index: usize, max_index: usize) -> Result<Self> {
+        let n_sample = self.len()?;
+        if min_index >= max_index || max_index > n_sample {
+            return Err(WindLoadsError::IndexRange {
+                min_index,
+                max_index,
+                n_sample,
+            });
+        }
+        self.slice(min_index, max_index);
+        Ok(self)
+    }
+    /// Keeps the samples of the loads and of the time vector with indices in [`min_index`,`max_index`[
+    fn slice(&mut self, min_index: usize, max_index: usize) {
+        self.for_each_loads(|x| x.range(min_index, max_index));
+        self.time = self
+            .time
+            .iter()
+            .skip(min_index)
+            .take(max_index - min_index)
+            .cloned()
+            .collect();
+    }
+    pub fn decimate(mut self, decimation_rate: usize) -> Self {
+        self.for_each_loads(|x| x.decimate(decimation_rate));
+        self.time = self.time.iter().step_by(decimation_rate).cloned().collect();
+        self
+    }
+    /// Decimates the loads of the wind loads `source` only, see [`Loads::decimate`]
+    ///
+    /// This harmonizes a source recorded at a multiple of the sampling frequency of the other sources,
+    /// the time vector is shared by all the sources and is left unchanged:
+    /// the decimated loads are trimmed to the length of the time vector
+    /// and an error is returned if they are shorter than the time vector
+    pub fn decimate_source(mut self, source: &str, decimation_rate: usize) -> Result<Self> {
+        assert!(
+            decimation_rate > 0,
+            "decimation_rate must be greater than 0"
+        );
+        let n_sample = self.time.len();
+        let loads = self.source_mut(source)?;
+        let n_decimated = loads.len().div_ceil(decimation_rate);
+        if n_decimated < n_sample {
+            return Err(WindLoadsError::Mismatch {
+                expected: n_sample,
+                got: n_decimated,
+                source: source.to_string(),
+            });
+        }
+        loads.decimate(decimation_rate);
+        loads.range(0, n_sample);
+        Ok(self)
+    }
+    /// Returns a copy of the wind loads within the time range [`t_min`,`t_max`[
+    ///
+    /// The loads selected for building are not copied,
+    /// an error is returned if there is no time sample within the time range
+    pub fn window(&self, t_min: f64, t_max: f64) -> Result<WindLoads> {
+        let (min_index, max_index) = self.time_indices(t_min, t_max);
+        if min_index == max_index {
+            return Err(WindLoadsError::Empty);
+        }
+        let loads = self
+            .loads
+            .iter()
+            .map(|x| {
+                x.as_ref().map(|x| {
+                    let io = x.as_slice();
+                    x.with_io(io[min_index.min(io.len())..max_index.min(io.len())].to_vec())
+                })
+            })
+            .collect();
+        Ok(
+            WindLoads::from_loads(self.time[min_index..max_index].to_vec(), loads)
+                .with_units(self.units),
+        )
+    }
+    /// Decimates the time series to the sampling frequency `target_hz`
+    ///
+    /// The decimation rate is the nearest integer to the ratio of the sampling frequency,
+    /// estimated from the time vector, to `target_hz`
+    pub fn decimate_to(self, target_hz: f64) -> Result<Self> {
+        assert!(target_hz > 0f64, "target_hz must be greater than 0");
+        let sampling_hz = self
+            .sampling_frequency()
+            .ok_or(WindLoadsError::SamplingFrequency)?;
+        let ratio = sampling_hz / target_hz;
+        let decimation_rate = (ratio.round() as usize).max(1);
+        if (ratio - decimation_rate as f64).abs() > 1e-6 {
+            log::warn!(
+                "non-integer decimation ratio {:.3} ({}Hz to {}Hz), decimating by {} to {}Hz",
+                ratio,
+                sampling_hz,
+                target_hz,
+                decimation_rate,
+                sampling_hz / decimation_rate as f64
+            );
+        }
+        Ok(self.decimate(decimation_rate))
+    }
+    /// Low-pass filters and decimates the time series, see [`Loads::decimate_filtered`]
+    pub fn decimate_filtered(mut self, decimation_rate: usize) -> Self {
+        self.for_each_loads(|x| x.decimate_filtered(decimation_rate));
+        self.time = self.time.iter().step_by(decimation_rate).cloned().collect();
+        self
+    }
+    /// Tapers the loads of each wind loads source at both ends of the time series, see [`Loads::taper_edges`]
+    pub fn taper_edges(mut self, n: usize) -> Self {
+        self.for_each_loads(|x| x.taper_edges(n));
+        self
+    }
+    /// Smooths the loads with a centered moving average, see [`Loads::moving_average`]
+    pub fn smooth(mut self, window: usize) -> Self {
+        self.for_each_loads(|x| x.moving_average(window));
+        self
+    }
+    /// Removes the temporal mean of each component of the loads, see [`Loads::detrend_mean`]
+    ///
+    /// Returns the detrended loads and the means that have been subtracted from each source
+    pub fn detrend_mean(mut self) -> (Self, HashMap<String, Vec<f64>>) {
+        let means = self
+            .loads
+            .iter_mut()
+            .filter_map(|x| x.as_mut())
+            .map(|x| (x.name().to_string(), x.detrend_mean()))
+            .collect();
+        (self, means)
+    }
+    /// Normalized cross-correlation between the component `comp_a` of `source_a` and the component `comp_b` of `source_b`
+    ///
+    /// Returns the pairs (lag, correlation) for lags in `-max_lag..=max_lag` samples,
+    /// a positive lag means that `source_b` lags behind `source_a`;
+    /// the time vector must be uniform, see [`assert_uniform_time`](WindLoads::assert_uniform_time)
+    pub fn xcorr(
+        &self,
+        source_a: &str,
+        comp_a: usize,
+        source_b: &str,
+        comp_b: usize,
+        max_lag: usize,
+    ) -> Result<Vec<(isize, f64)>> {
+        self.assert_uniform_time(TIME_STEP_TOLERANCE)?;
+        let a = self.source_component(source_a, comp_a)?;
+        let b = self.source_component(source_b, comp_b)?;
+        if a.is_empty() || b.is_empty() {
+            return Err(WindLoadsError::Empty);
+        }
+        Ok(signal::xcorr(&a, &b, max_lag))
+    }
+    /// Magnitude-squared coherence between the component `comp_a` of `source_a` and the component `comp_b` of `source_b`
+    ///
+    /// The cross and auto power spectral densities are estimated with Welch's method, see [`Loads::psd`].
+    /// Returns the pairs (frequency, coherence) from 0 to the Nyquist frequency, `nfft` must not exceed the number of samples;
+    /// the time vector must be uniform, see [`assert_uniform_time`](WindLoads::assert_uniform_time)
+    pub fn coherence(
+        &self,
+        source_a: &str,
+        comp_a: usize,
+        source_b: &str,
+        comp_b: usize,
+        sampling_hz: f64,
+        nfft: usize,
+    ) -> Result<Vec<(f64, f64)>> {
+        self.assert_uniform_time(TIME_STEP_TOLERANCE)?;
+        let a = self.source_component(source_a, comp_a)?;
+        let b = self.source_component(source_b, comp_b)?;
+        let n_sample = a.len().min(b.len());
+        if nfft < 2 || nfft > n_sample {
+            return Err(WindLoadsError::Nfft { nfft, n_sample });
+        }
+        let paa = signal::welch_csd(&a, &a, sampling_hz, nfft);
+        let pbb = signal::welch_csd(&b, &b, sampling_hz, nfft);
+        let pab = signal::welch_csd(&a, &b, sampling_hz, nfft);
+        Ok(pab
+            .into_iter()
+            .zip(paa.into_iter().zip(pbb))
+            .map(|((f, pab), ((_, paa), (_, pbb)))| {
+                let p = paa.re * pbb.re;
+                (f, if p > 0f64 { pab.norm_sqr() / p } else { 0f64 })
+            })
+            .collect())
+    }
+    /// Returns the statistics of each wind loads source, see [`Loads::stats`]
+    pub fn stats(&self) -> HashMap<String, LoadStats> {
+        self.loads
+            .iter()
+            .filter_map(|x| x.as_ref())
+            .map(|x| (x.name().to_string(), x.stats()))
+            .collect()
+    }
+    /// Ramps up the loads over the first `rise_time_s` seconds
+    ///
+    /// The loads are multiplied by a sigmoid function that rises from 0 to 1 within `rise_time_s`
+    pub fn ramp_up(mut self, rise_time_s: f64, sampling_hz: f64) -> Self {
+        assert!(rise_time_s > 0f64, "rise_time_s must be greater than 0");
+        let n = (rise_time_s * sampling_hz).ceil() as usize;
+        let sigmoid = |t: f64| 1. / (1. + (-12. * (t / rise_time_s - 0.5)).exp());
+        let (s0, s1) = (sigmoid(0f64), sigmoid(rise_time_s));
+        let weights: Vec<f64> = (0..n)
+            .map(|i| (sigmoid(i as f64 / sampling_hz) - s0) / (s1 - s0))
+            .collect();
         self.loads
             .iter_mut()
             .filter_map(|x| x.as_mut())
             .for_each(|x| {
-                x.range(min_index, max_index);
+                x.io_mut()
+                    .iter_mut()
+                    .zip(&weights)
+                    .for_each(|(x, w)| x.iter_mut().for_each(|x| *x *= w));
             });
         self
     }
-    pub fn decimate(mut self, decimation_rate: usize) -> Self {
+    /// Adds sample-wise the loads of `other` to the loads
+    ///
+    /// The sources present in only one of the data sets are kept unchanged,
+    /// the loads of `other` are converted to the units of the loads before being added
+    pub fn superpose(mut self, other: &WindLoads) -> Result<Self> {
+        let converted;
+        let other = if other.units == self.units {
+            other
+        } else {
+            log::warn!(
+                "superposing wind loads in {} to wind loads in {}, converting units",
+                other.units,
+                self.units
+            );
+            converted = WindLoads::from_loads(other.time.clone(), other.loads.clone())
+                .with_units(other.units)
+                .convert_units(self.units);
+            &converted
+        };
+        for other_loads in other.loads.iter().filter_map(|x| x.as_ref()) {
+            match self
+                .loads
+                .iter_mut()
+                .filter_map(|x| x.as_mut())
+                .find(|x| x.name() == other_loads.name())
+            {
+                Some(loads) => {
+                    if loads.len() != other_loads.len() {
+                        return Err(WindLoadsError::Len);
+                    }
+                    loads
+                        .io_mut()
+                        .iter_mut()
+                        .zip(other_loads.as_slice())
+                        .for_each(|(x, y)| x.iter_mut().zip(y).for_each(|(x, y)| *x += y));
+                    debug_assert!(
+                        loads.is_rectangular(),
+                        "{} samples are jagged",
+                        loads.name()
+                    );
+                }
+                None => self.loads.push(Some(other_loads.clone())),
+            }
+        }
+        Ok(self)
+    }
+    /// Multiplies the loads of the wind loads `source` by `gain`
+    pub fn scale(mut self, source: &str, gain: f64) -> Result<Self> {
+        self.source_mut(source)?.scale(gain);
+        Ok(self)
+    }
+    /// Adds a constant offset to the loads of the wind loads `source`, see [`Loads::add_offset`]
+    pub fn add_offset(mut self, source: &str, offset: &[f64]) -> Result<Self> {
+        self.source_mut(source)?.add_offset(offset)?;
+        Ok(self)
+    }
+    /// Zeroes the components of the wind loads `source` that are masked out by `dof_mask`, see [`Loads::keep_dofs`]
+    ///
+    /// The sample length is preserved so the loads still match the FEM input
+    pub fn keep_dofs(mut self, source: &str, dof_mask: &[bool]) -> Result<Self> {
+        self.source_mut(source)?.keep_dofs(dof_mask)?;
+        Ok(self)
+    }
+    /// Rotates the forces and the moments of the wind loads `source`, see [`Loads::rotate`]
+    pub fn rotate(mut self, source: &str, rotation: [[f64; 3]; 3]) -> Result<Self> {
+        self.source_mut(source)?.rotate(rotation)?;
+        Ok(self)
+    }
+    /// Shifts the time vector so that it starts at 0, the loads are left unchanged
+    pub fn zero_time(mut self) -> Self {
+        if let Some(&t0) = self.time.first() {
+            self.time.iter_mut().for_each(|t| *t -= t0);
+        }
+        self
+    }
+    /// Pads the loads with zeros up to `n_samples` samples, see [`Loads::pad_to`]
+    ///
+    /// The time vector is extended with time steps of `dt`
+    pub fn pad_to(mut self, n_samples: usize, dt: f64) -> Self {
+        self.for_each_loads(|x| x.pad_to(n_samples));
+        if let Some(&t_last) = self.time.last() {
+            let n = self.time.len();
+            self.time
+                .extend((1..=n_samples.saturating_sub(n)).map(|i| t_last + i as f64 * dt));
+        }
+        self
+    }
+    /// Keeps only the bodies `indices` of the wind loads `source`, see [`Loads::select_bodies`]
+    ///
+    /// Each body is assumed to be made of 6 components: the forces and the moments
+    pub fn select_bodies(mut self, source: &str, indices: &[usize]) -> Result<Self> {
+        self.source_mut(source)?.select_bodies(indices, 6)?;
+        Ok(self)
+    }
+    /// Multiplies the loads of all the wind loads sources by `gain`
+    pub fn scale_all(mut self, gain: f64) -> Self {
+        self.loads
+            .iter_mut()
+            .filter_map(|x| x.as_mut())
+            .for_each(|x| x.scale(gain));
+        self
+    }
+    /// Normalizes each component of each wind loads source to a unit RMS
+    ///
+    /// Returns the normalized loads and the RMS of each component of each source,
+    /// the components with a zero RMS are left unchanged
+    pub fn normalize_rms(mut self) -> (Self, HashMap<String, Vec<f64>>) {
+        let mut rms = HashMap::new();
+        for loads in self.loads.iter_mut().filter_map(|x| x.as_mut()) {
+            let factors = loads.stats().rms;
+            loads.io_mut().iter_mut().for_each(|x| {
+                x.iter_mut()
+                    .zip(&factors)
+                    .filter(|(_, r)| **r > 0f64)
+                    .for_each(|(x, r)| *x /= r)
+            });
+            rms.insert(loads.name().to_string(), factors);
+        }
+        (self, rms)
+    }
+    /// Appends the loads of `other` at the end of the loads
+    ///
+    /// The time vector of `other` is shifted to start one time step after the last time sample.
+    /// Both data sets must contain the same wind loads sources.
+    pub fn append(mut self, other: WindLoads) -> Result<Self> {
+        let shift = match (self.time.as_slice(), other.time.as_slice()) {
+            ([.., t0, t1], [t, ..]) => 2. * t1 - t0 - t,
+            ([t1], [t0, t, ..]) => t1 + t - 2. * t0,
+            _ => 0f64,
+        };
+        let time = self.extend_loads(other)?;
+        self.time.extend(time.into_iter().map(|t| t + shift));
+        Ok(self)
+    }
+    /// Appends the loads of `other` at the end of the loads and returns the time vector of `other`
+    ///
+    /// Both data sets must contain the same wind loads sources,
+    /// the loads of `other` are converted to the units of the loads
+    fn extend_loads(&mut self, other: WindLoads) -> Result<Vec<f64>> {
+        let other = other.convert_units(self.units);
+        let (self_names, other_names) = (self.available_sources(), other.available_sources());
+        if let Some(name) = self_names
+            .iter()
+            .find(|x| !other_names.contains(x))
+            .or_else(|| other_names.iter().find(|x| !self_names.contains(x)))
+        {
+            return Err(WindLoadsError::MissingSource(name.clone()));
+        }
+        for other_loads in other.loads.into_iter().flatten() {
+            let name = other_loads.name();
+            let loads = self.source_mut(name)?;
+            loads.io_mut().extend(other_loads.io());
+            debug_assert!(loads.is_rectangular(), "{} samples are jagged", name);
+        }
+        Ok(other.time)
+    }
+    /// Resamples the time series from the `from_hz` to the `to_hz` sampling frequency
+    ///
+    /// The loads are linearly interpolated onto the new uniform time grid starting at the first sample,
+    /// the last sample is preserved only if it falls on the new grid, otherwise the grid stops at the last grid point before it;
+    /// the time vector must be uniform, see [`assert_uniform_time`](WindLoads::assert_uniform_time)
+    pub fn resample(mut self, from_hz: f64, to_hz: f64) -> Result<Self> {
+        assert!(
+            from_hz > 0f64 && to_hz > 0f64,
+            "sampling frequencies must be greater than 0"
+        );
+        self.assert_uniform_time(TIME_STEP_TOLERANCE)?;
+        let n = self.len()?;
+        if n == 0 {
+            return Err(WindLoadsError::Empty);
+        }
+        let t0 = *self.time.first().ok_or(WindLoadsError::Empty)?;
+        let ratio = from_hz / to_hz;
+        let n_resampled = ((n - 1) as f64 / ratio + 1e-9).floor() as usize + 1;
+        let positions: Vec<f64> = (0..n_resampled)
+            .map(|k| {
+                let p = k as f64 * ratio;
+                if (p - p.round()).abs() < 1e-9 {
+                    p.round()
+                } else {
+                    p
+                }
+            })
+            .collect();
+        let time: Vec<f64> = (0..n_resampled).map(|k| t0 + k as f64 / to_hz).collect();
         self.loads
             .iter_mut()
             .filter_map(|x| x.as_mut())
             .for_each(|x| {
-                x.decimate(decimation_rate);
+                x.interpolate(&positions);
             });
-        self
+        self.time = time;
+        Ok(self)
     }
-    fn tagged_load(&self, io: &Tags) -> Result<Outputs> {
-        match &self.n_sample {
-            Some(n) => self
-                .loads
-                .iter()
-                .find_map(|x| x.as_ref().and_then(|x| io.ndata(x, *n)))
-                .map_or(Err(WindLoadsError::Empty), |x| Ok(Some(x))),
-            None => self
-                .loads
+    /// Linear interpolation of the loads onto the time vector `new_time`
+    ///
+    /// `new_time` may be non-uniform, the loads at times outside the span of the current time vector
+    /// are clamped to the first or last sample
+    pub fn interpolate_onto(mut self, new_time: &[f64]) -> Result<Self> {
+        let n = self.len()?.min(self.time.len());
+        if n == 0 {
+            return Err(WindLoadsError::Empty);
+        }
+        let time = &self.time[..n];
+        let positions: Vec<f64> = new_time
+            .iter()
+            .map(|&t| {
+                let i = time.partition_point(|&x| x <= t);
+                if i == 0 {
+                    0f64
+                } else if i == n {
+                    (n - 1) as f64
+                } else {
+                    let (t0, t1) = (time[i - 1], time[i]);
+                    (i - 1) as f64 + (t - t0) / (t1 - t0)
+                }
+            })
+            .collect();
+        self.loads
+            .iter_mut()
+            .filter_map(|x| x.as_mut())
+            .for_each(|x| {
+                x.interpolate(&positions);
+            });
+        self.time = new_time.to_vec();
+        Ok(self)
+    }
+    /// First-order hold interpolation of the loads at the simulation sampling frequency
+    ///
+    /// The sampling frequency of the loads is derived from the time vector
+    pub fn foh(self, sim_sampling_frequency: usize) -> Result<Self> {
+        let (t0, t1) = match (self.time.first(), self.time.last()) {
+            (Some(t0), Some(t1)) if t1 > t0 => (*t0, *t1),
+            _ => return Err(WindLoadsError::Len),
+        };
+        let from_hz = (self.time.len() - 1) as f64 / (t1 - t0);
+        self.resample(from_hz, sim_sampling_frequency as f64)
+    }
+    /// Applies `f` to the loads of each wind loads source
+    ///
+    /// The sources are processed in parallel with the `parallel` feature
+    fn for_each_loads<F>(&mut self, f: F)
+    where
+        F: Fn(&mut Loads) + Send + Sync,
+    {
+        #[cfg(feature = "parallel")]
+        self.loads
+            .par_iter_mut()
+            .filter_map(|x| x.as_mut())
+            .for_each(f);
+        #[cfg(not(feature = "parallel"))]
+        self.loads.iter_mut().filter_map(|x| x.as_mut()).for_each(f);
+    }
+    /// Returns a reference to the loads of the wind loads `source`
+    fn source(&self, source: &str) -> Result<&Loads> {
+        if !wind_loads_name().contains(&source) {
+            return Err(WindLoadsError::UnknownSource(source.to_string()));
+        }
+        self.loads
+            .iter()
+            .filter_map(|x| x.as_ref())
+            .find(|x| x.name() == source)
+            .ok_or_else(|| missing_source(&self.selected_sources, source))
+    }
+    /// Returns the time series of the component `component` of the wind loads `source`
+    fn source_component(&self, source: &str, component: usize) -> Result<Vec<f64>> {
+        self.source(source)?.checked_component_series(component)
+    }
+    /// Returns a mutable reference to the loads of the wind loads `source`
+    fn source_mut(&mut self, source: &str) -> Result<&mut Loads> {
+        if !wind_loads_name().contains(&source) {
+            return Err(WindLoadsError::UnknownSource(source.to_string()));
+        }
+        let selected = &self.selected_sources;
+        self.loads
+            .iter_mut()
+            .filter_map(|x| x.as_mut())
+            .find(|x| x.name() == source)
+            .ok_or_else(|| missing_source(selected, source))
+    }
+    /// Returns a copy of the wind loads matching `io`
+    ///
+    /// The loads are truncated to the number of samples set with [`n_sample`](WindLoads::n_sample);
+    /// unlike the selectors, the loads are left in place
+    pub fn load_for(&self, io: &Tags) -> Result<Option<std::vec::IntoIter<Vec<f64>>>> {
+        let loads = self
+            .loads
+            .iter()
+            .filter_map(|x| x.as_ref())
+            .find(|x| io.matches(x))
+            .ok_or_else(|| {
+                WindLoadsError::MissingSource(io_source_name(io).unwrap_or(io_name(io)).to_string())
+            })?;
+        let n = self.n_sample.unwrap_or_else(|| loads.len());
+        if n > loads.len() {
+            return Err(WindLoadsError::Mismatch {
+                expected: n,
+                got: loads.len(),
+                source: loads.name().to_string(),
+            });
+        }
+        Ok(io.ndata(loads, n))
+    }
+    /// Moves the wind loads matching `io` out of the loads
+    ///
+    /// The loads of a source that has already been selected are copied from the selected loads, see [selection](WindLoads#selection)
+    fn tagged_load(&mut self, io: &Tags) -> Result<Outputs> {
+        let name = io_source_name(io).unwrap_or(io_name(io));
+        let Some(slot) = self
+            .loads
+            .iter()
+            .position(|x| x.as_ref().is_some_and(|x| io.matches(x)))
+        else {
+            if !self.selected_sources.iter().any(|(x, _)| *x == name) {
+                return Err(missing_source(&self.selected_sources, name));
+            }
+            let data = self
+                .tagged_loads
                 .iter()
-                .find_map(|x| x.as_ref().and_then(|x| io.data(x)))
-                .map_or(Err(WindLoadsError::Empty), |x| Ok(Some(x))),
+                .find(|(source, _)| *source == Some(name))
+                .and_then(|(_, x)| io_data(x))
+                .ok_or_else(|| WindLoadsError::AlreadySelected(name.to_string()))?;
+            let n = self.n_sample.unwrap_or(data.len());
+            return Ok(Some(
+                data.as_slice()
+                    .iter()
+                    .take(n)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            ));
+        };
+        let entry = &mut self.loads[slot];
+        if let (Some(n), Some(loads)) = (self.n_sample, entry.as_ref()) {
+            if n > loads.len() {
+                return Err(WindLoadsError::Mismatch {
+                    expected: n,
+                    got: loads.len(),
+                    source: loads.name().to_string(),
+                });
+            }
         }
+        let loads = entry.take().ok_or(WindLoadsError::Empty)?;
+        self.selected_sources.push((name, slot));
+        let data = io.move_data(loads).ok_or(WindLoadsError::Empty)?;
+        Ok(Some(match &self.n_sample {
+            Some(n) => data.take(*n).collect::<Vec<_>>().into_iter(),
+            None => data,
+        }))
     }
     /// Set the number of time sample
-    pub fn n_sample(self, n_sample: usize) -> Result<Self> {
+    ///
+    /// The time vector is truncated to the first `n_sample` samples
+    pub fn n_sample(mut self, n_sample: usize) -> Result<Self> {
         assert!(n_sample > 0, "n_sample must be greater than 0");
         let n = self.len()?;
         assert!(
@@ -240,73 +2196,285 @@ impl WindLoads {
             "n_sample cannot be greater than the number of sample ({})",
             n
         );
+        let n_sample = if n_sample <= n { n_sample } else { n };
+        self.time.truncate(n_sample);
         Ok(Self {
-            n_sample: Some(if n_sample <= n { n_sample } else { n }),
+            n_sample: Some(n_sample),
             ..self
         })
     }
     /// Selects loads on the truss
+    ///
+    /// The loads are moved out of the data, see [selection](WindLoads#selection)
     pub fn truss(mut self) -> Result<Self> {
-        self.tagged_loads.push(IO::OSSTruss6F {
-            data: self.tagged_load(&jar::OSSTruss6F::io())?,
-        });
+        self.select_tagged(&jar::OSSTruss6F::io(), &jar::OSSTruss6F::io())?;
         Ok(self)
     }
     /// Selects loads on the top-end
+    ///
+    /// The loads are moved out of the data, see [selection](WindLoads#selection)
     pub fn topend(mut self) -> Result<Self> {
-        self.tagged_loads.push(IO::OSSTopEnd6F {
-            data: self.tagged_load(&jar::OSSTopEnd6F::io())?,
-        });
+        self.select_tagged(&jar::OSSTopEnd6F::io(), &jar::OSSTopEnd6F::io())?;
         Ok(self)
     }
+    /// Selects the top-end loads and associates them with the M2 ASM top-end FEM input
+    ///
+    /// The loads are moved out of the data, see [selection](WindLoads#selection)
     pub fn m2_asm_topend(mut self) -> Result<Self> {
-        self.tagged_loads.push(IO::MCM2TE6F {
-            data: self.tagged_load(&jar::OSSTopEnd6F::io())?,
-        });
+        self.select_tagged(&jar::OSSTopEnd6F::io(), &jar::MCM2TE6F::io())?;
         Ok(self)
     }
     /// Selects loads on the C-ring
+    ///
+    /// The loads are moved out of the data, see [selection](WindLoads#selection)
     pub fn cring(mut self) -> Result<Self> {
-        self.tagged_loads.push(IO::OSSCRING6F {
-            data: self.tagged_load(&jar::OSSCRING6F::io())?,
-        });
+        self.select_tagged(&jar::OSSCRING6F::io(), &jar::OSSCRING6F::io())?;
         Ok(self)
     }
     /// Selects loads on the GIR
+    ///
+    /// The loads are moved out of the data, see [selection](WindLoads#selection)
     pub fn gir(mut self) -> Result<Self> {
-        self.tagged_loads.push(IO::OSSGIR6F {
-            data: self.tagged_load(&jar::OSSGIR6F::io())?,
-        });
+        self.select_tagged(&jar::OSSGIR6F::io(), &jar::OSSGIR6F::io())?;
         Ok(self)
     }
     /// Selects loads on the M1 cells
+    ///
+    /// The loads are moved out of the data, see [selection](WindLoads#selection)
     pub fn m1_cell(mut self) -> Result<Self> {
-        self.tagged_loads.push(IO::OSSCellLcl6F {
-            data: self.tagged_load(&jar::OSSCellLcl6F::io())?,
-        });
+        self.select_tagged(&jar::OSSCellLcl6F::io(), &jar::OSSCellLcl6F::io())?;
         Ok(self)
     }
     /// Selects loads on the M1 segments
+    ///
+    /// The loads are moved out of the data, see [selection](WindLoads#selection)
     pub fn m1_segments(mut self) -> Result<Self> {
-        self.tagged_loads.push(IO::OSSM1Lcl6F {
-            data: self.tagged_load(&jar::OSSM1Lcl6F::io())?,
-        });
+        self.select_tagged(&jar::OSSM1Lcl6F::io(), &jar::OSSM1Lcl6F::io())?;
+        Ok(self)
+    }
+    /// Selects loads on the M1 segments rotated from the segment local frames into the OSS frame
+    ///
+    /// The forces and moments of each segment are rotated according to [`M1_SEGMENT_ORIENTATIONS`],
+    /// the loads are still associated with the FEM input `OSS_M1_lcl_6F` as there is no M1 segments global FEM input.
+    /// The loads are moved out of the data and cannot be copied from an earlier selection, see [selection](WindLoads#selection)
+    pub fn m1_segments_global(mut self) -> Result<Self> {
+        let rotations: Vec<_> = M1_SEGMENT_ORIENTATIONS
+            .iter()
+            .map(|x| m1_segment_rotation(*x))
+            .collect();
+        self.source_mut("OSS_M1_lcl_6F")?
+            .rotate_bodies(&rotations)?;
+        self.select_tagged(&jar::OSSM1Lcl6F::io(), &jar::OSSM1Lcl6F::io())?;
         Ok(self)
     }
     /// Selects loads on the M2 segments
+    ///
+    /// The loads are moved out of the data, see [selection](WindLoads#selection)
     pub fn m2_segments(mut self) -> Result<Self> {
-        self.tagged_loads.push(IO::MCM2Lcl6F {
-            data: self.tagged_load(&jar::MCM2Lcl6F::io())?,
-        });
+        self.select_tagged(&jar::MCM2Lcl6F::io(), &jar::MCM2Lcl6F::io())?;
         Ok(self)
     }
+    /// Selects the M2 segments loads and associates them with the M2 ASM reference bodies FEM input
+    ///
+    /// The loads are moved out of the data, see [selection](WindLoads#selection)
     pub fn m2_asm_reference_bodies(mut self) -> Result<Self> {
-        self.tagged_loads.push(IO::MCM2RB6F {
-            data: self.tagged_load(&jar::MCM2Lcl6F::io())?,
-        });
+        self.select_tagged(&jar::MCM2Lcl6F::io(), &jar::MCM2RB6F::io())?;
+        Ok(self)
+    }
+    /// Selects loads by the name of the wind loads source
+    ///
+    /// The loads are moved out of the data, see [selection](WindLoads#selection)
+    pub fn select(mut self, name: &str) -> Result<Self> {
+        self.select_by_name(name)?;
         Ok(self)
     }
+    fn select_by_name(&mut self, name: &str) -> Result<()> {
+        let tag: Tags = io_from_name(name, ())
+            .ok_or_else(|| WindLoadsError::UnknownSource(name.to_string()))?;
+        self.select_tagged(&tag, &tag)
+    }
+    /// Moves the wind loads matching `io` out of the loads and associates them with the FEM input `fem`
+    fn select_tagged(&mut self, io: &Tags, fem: &Tags) -> Result<()> {
+        let data = self.tagged_load(io)?;
+        let source = io_source_name(io);
+        self.tagged_loads
+            .extend(io_retag(fem, data).map(|x| (source, x)));
+        Ok(())
+    }
+    /// Selects the loads of the wind loads source `name` and associates them with the FEM input `fem`
+    ///
+    /// `name` is one of the names returned by [`wind_loads_name`],
+    /// the loads are moved out of the data, see [selection](WindLoads#selection)
+    pub fn select_into(mut self, name: &str, fem: Tags) -> Result<Self> {
+        io_retag::<(), _>(&fem, None).ok_or(WindLoadsError::UnsupportedTag)?;
+        let tag: Tags = io_from_name(name, ())
+            .ok_or_else(|| WindLoadsError::UnknownSource(name.to_string()))?;
+        self.select_tagged(&tag, &fem)?;
+        Ok(self)
+    }
+    /// Selects all the loads present in the data
+    ///
+    /// The sources are selected in the same order than [`select_all`](WindLoads::select_all)
+    /// and the names of the missing sources are available with [`skipped_sources`](WindLoads::skipped_sources);
+    /// an error is returned if a source that is present cannot be selected, e.g. it is shorter than [`n_sample`](WindLoads::n_sample)
+    pub fn select_available(mut self) -> Result<Self> {
+        let available = self.available_sources();
+        for name in SELECT_ALL {
+            if available.iter().any(|x| x == name) {
+                self.select_by_name(name)?;
+            } else {
+                self.skipped_sources.push(name.to_string());
+            }
+        }
+        Ok(self)
+    }
+    /// Removes the loads of the wind loads source `name` from the selection
+    ///
+    /// All the FEM inputs fed by the source are removed and the loads are put back into the data,
+    /// so the source can be selected again; nothing happens if the source has not been selected.
+    /// The loads of a source merged with another one cannot be deselected
+    pub fn deselect(mut self, name: &str) -> Result<Self> {
+        let name =
+            source_name(name).ok_or_else(|| WindLoadsError::UnknownSource(name.to_string()))?;
+        let Some(index) = self.selected_sources.iter().position(|(x, _)| *x == name) else {
+            return Ok(self);
+        };
+        let mut data = None;
+        for (source, io) in std::mem::take(&mut self.tagged_loads) {
+            if source == Some(name) {
+                data = data.or_else(|| io_data(&io).map(|x| x.as_slice().to_vec()));
+            } else {
+                self.tagged_loads.push((source, io));
+            }
+        }
+        let data = data.ok_or_else(|| WindLoadsError::AlreadySelected(name.to_string()))?;
+        let (_, slot) = self.selected_sources.remove(index);
+        self.loads[slot] = loads_from_name(name, data);
+        Ok(self)
+    }
+    /// Returns the names of the sources skipped by [`select_available`](WindLoads::select_available)
+    pub fn skipped_sources(&self) -> &[String] {
+        &self.skipped_sources
+    }
+    /// Returns the tags of the wind loads selected so far, in the order of the [`WindLoading`] outputs
+    pub fn selected_tags(&self) -> Vec<Tags> {
+        self.tagged_loads.iter().map(|(_, x)| x.into()).collect()
+    }
+    /// Returns the sample length of each selected wind loads, keyed by the name of its FEM input
+    ///
+    /// The sample length is the number of FEM inputs fed by the wind loads
+    pub fn input_widths(&self) -> HashMap<String, usize> {
+        self.tagged_loads
+            .iter()
+            .map(|(_, x)| {
+                let width = io_data(x)
+                    .and_then(|x| x.as_slice().first())
+                    .map_or(0, |x| x.len());
+                (io_name(x).to_string(), width)
+            })
+            .collect()
+    }
+    /// Returns the forces and moments of each selected wind loads at the time sample `index`
+    ///
+    /// The loads are given in the order of the [`WindLoading`] outputs and are named after their wind loads source,
+    /// or after their FEM input for merged sources
+    pub fn snapshot(&self, index: usize) -> Result<Vec<(String, Vec<f64>)>> {
+        self.tagged_loads
+            .iter()
+            .map(|(_, x)| {
+                let data = io_data(x).map_or(&[][..], |x| x.as_slice());
+                let sample = data.get(index).ok_or(WindLoadsError::IndexRange {
+                    min_index: index,
+                    max_index: index + 1,
+                    n_sample: data.len(),
+                })?;
+                let name = io_source_name(x).unwrap_or(io_name(x));
+                Ok((name.to_string(), sample.clone()))
+            })
+            .collect()
+    }
+    /// Sorts the selected wind loads in the order of the names in `order`
+    ///
+    /// The names are either the wind loads source names or the names of the FEM inputs (e.g. `"OSSTruss6F"`);
+    /// each selected wind loads must be named exactly once
+    pub fn reorder(mut self, order: &[&str]) -> Result<Self> {
+        let mut tagged_loads: Vec<_> = self.tagged_loads.drain(..).map(Some).collect();
+        for name in order {
+            let index = tagged_loads
+                .iter()
+                .position(|x| {
+                    x.as_ref().is_some_and(|(_, x)| {
+                        io_name(x) == *name || io_source_name(x) == Some(*name)
+                    })
+                })
+                .ok_or_else(|| {
+                    if order.iter().filter(|x| *x == name).count() > 1 {
+                        WindLoadsError::DuplicateSource(name.to_string())
+                    } else {
+                        WindLoadsError::MissingSource(name.to_string())
+                    }
+                })?;
+            self.tagged_loads.extend(tagged_loads[index].take());
+        }
+        if let Some((_, x)) = tagged_loads.into_iter().flatten().next() {
+            return Err(WindLoadsError::UnorderedSource(
+                io_source_name(&x).unwrap_or(io_name(&x)).to_string(),
+            ));
+        }
+        Ok(self)
+    }
+    /// Selects loads on the M2 segments and associates them with the FEM input `fem`
+    ///
+    /// The loads are moved out of the data, see [selection](WindLoads#selection)
+    pub fn m2_asm_reference_bodies_into(self, fem: Tags) -> Result<Self> {
+        self.select_into("MC_M2_lcl_force_6F", fem)
+    }
+    /// Selects the loads of the wind loads sources `a` and `b` and associates them with the FEM input `into`
+    ///
+    /// The samples of `b` are appended to the samples of `a` at each time step,
+    /// both sources must have the same number of samples;
+    /// the loads are moved out of the data, see [selection](WindLoads#selection)
+    pub fn merge_sources(mut self, a: &str, b: &str, into: Tags) -> Result<Self> {
+        io_retag::<(), _>(&into, None).ok_or(WindLoadsError::UnsupportedTag)?;
+        let (n_a, n_b) = (self.source(a)?.len(), self.source(b)?.len());
+        if n_a != n_b {
+            return Err(WindLoadsError::Mismatch {
+                expected: n_a,
+                got: n_b,
+                source: b.to_string(),
+            });
+        }
+        let data_a = self.select_data(a)?;
+        let data_b = self.select_data(b)?;
+        let merged: Vec<Vec<f64>> = data_a
+            .into_iter()
+            .flatten()
+            .zip(data_b.into_iter().flatten())
+            .map(|(mut x, y)| {
+                x.extend(y);
+                x
+            })
+            .collect();
+        debug_assert!(
+            is_rectangular(&merged),
+            "{} and {} merged samples are jagged",
+            a,
+            b
+        );
+        self.tagged_loads
+            .extend(io_retag(&into, Some(merged.into_iter())).map(|x| (None, x)));
+        Ok(self)
+    }
+    /// Moves the loads of the wind loads source `name` out of the loads
+    fn select_data(&mut self, name: &str) -> Result<Outputs> {
+        let tag: Tags = io_from_name(name, ())
+            .ok_or_else(|| WindLoadsError::UnknownSource(name.to_string()))?;
+        self.tagged_load(&tag)
+    }
     /// Selects all loads
+    ///
+    /// All the sources returned by [`wind_loads_name`] are selected;
+    /// there is no mirror covers source, its loads are not read from the data files
     pub fn select_all(self) -> Result<Self> {
         self.topend()?
             .m2_segments()?
@@ -327,22 +2495,176 @@ impl WindLoads {
             .cring()
     }
     /// Builds a wind loading source object
+    ///
+    /// All the selected loads must have the number of samples set with [`n_sample`](WindLoads::n_sample)
+    /// or, by default, the number of samples of the first selected loads; the unselected loads are ignored
     pub fn build(self) -> Result<WindLoading> {
+        if self.checked {
+            self.validate()?;
+        }
+        let n_sample = match self.n_sample {
+            Some(n_sample) => n_sample,
+            None => self
+                .tagged_loads
+                .iter()
+                .find_map(|(_, x)| io_len(x))
+                .ok_or(WindLoadsError::Len)?,
+        };
+        if let Some((got, io)) = self
+            .tagged_loads
+            .iter()
+            .filter_map(|(_, x)| io_len(x).map(|n| (n, x)))
+            .find(|(n, _)| *n != n_sample)
+        {
+            return Err(WindLoadsError::Mismatch {
+                expected: n_sample,
+                got,
+                source: io_name(io).to_string(),
+            });
+        }
+        // A single copy of the time series is kept: as the loads of their wind loads source
+        // and as DOS `IO` data for the merged sources, the tags only keep the kind of `IO`
+        let (raw, samples): (Vec<_>, Vec<_>) = self
+            .tagged_loads
+            .iter()
+            .filter_map(|(source, x)| {
+                let tag = io_retag(x, None)?;
+                let data = io_data(x).map(|x| x.as_slice().to_vec());
+                Some(match (source, data) {
+                    (Some(source), Some(data)) => (loads_from_name(source, data), tag),
+                    (_, data) => (None, io_retag(x, data)?),
+                })
+            })
+            .unzip();
+        let mut time = self.time;
+        time.truncate(n_sample);
         Ok(WindLoading {
-            n_sample: self.n_sample.unwrap_or(self.len()?),
-            loads: self.tagged_loads,
+            n_sample,
+            loads: self.tagged_loads.into_iter().map(|(_, x)| x).collect(),
+            samples,
+            raw,
+            time,
+            units: self.units,
+            desynced: false,
         })
     }
 }
+impl fmt::Display for WindLoads {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.summary())
+    }
+}
 
 /// Wind loading sources
 ///
 /// This structure contains the time series of wind forces and moments.
-/// The time series implement the [`Iterator`] trait and the [`outputs`](crate::wind_loads::WindLoading::outputs) method step through the iterator
+/// The time series implement the [`Iterator`] trait and the [`outputs`](crate::wind_loads::WindLoading::outputs) method step through the iterator.
+/// A copy of the time series is kept to [`reset`](WindLoading::reset) the iterators
 #[derive(Default)]
 pub struct WindLoading {
     pub loads: Vec<IO<std::vec::IntoIter<Vec<f64>>>>,
     pub n_sample: usize,
+    // the data of the wind loads sources is kept in `raw` only
+    samples: Vec<IO<Vec<Vec<f64>>>>,
+    raw: Vec<Option<Loads>>,
+    time: Vec<f64>,
+    units: Units,
+    desynced: bool,
+}
+
+impl WindLoading {
+    /// Returns the names of the wind loads in the order of the outputs
+    ///
+    /// The loads are named after their wind loads source, or after their FEM input for merged sources
+    pub fn source_names(&self) -> Vec<String> {
+        self.outputs_tags()
+            .iter()
+            .map(|x| io_source_name(x).unwrap_or(io_name(x)).to_string())
+            .collect()
+    }
+    /// Returns the wind loads with the DOS `IO` tag `name` (e.g. `"OSSTruss6F"`)
+    /// or with the wind loads source name `name` (e.g. `"OSS_Truss_6F"`), see [`source_names`](WindLoading::source_names)
+    pub fn source(&self, name: &str) -> Option<&IO<std::vec::IntoIter<Vec<f64>>>> {
+        self.loads
+            .iter()
+            .find(|x| io_name(x) == name || io_source_name(x) == Some(name))
+    }
+    /// Returns the number of time steps left in the wind loads time series
+    pub fn remaining(&self) -> usize {
+        self.loads
+            .iter()
+            .map(|x| io_len(x).unwrap_or(0))
+            .min()
+            .unwrap_or(0)
+    }
+    /// Rewinds the wind loads time series to the first sample
+    pub fn reset(&mut self) {
+        self.loads = self
+            .samples
+            .iter()
+            .zip(&self.raw)
+            .filter_map(|(x, raw)| {
+                let data = match raw {
+                    Some(loads) => Some(loads.io_ref()),
+                    None => io_data(x),
+                };
+                io_retag(x, data.map(|x| x.clone().into_iter()))
+            })
+            .collect();
+        self.desynced = false;
+    }
+    /// Returns true if some of the wind loads time series have been exhausted before the others
+    pub fn desynced(&self) -> bool {
+        self.desynced
+    }
+    /// Returns a copy of the wind loads in the order of the outputs
+    ///
+    /// The copy is the one used to [`reset`](WindLoading::reset) the iterators,
+    /// the loads are labelled by the wind loads source they are selected from whatever the FEM input they feed,
+    /// the loads of merged sources are `None`
+    pub fn raw(&self) -> &[Option<Loads>] {
+        &self.raw
+    }
+    /// Converts the wind loading back into the [`WindLoads`] builder
+    ///
+    /// The loads are recovered from the copy of the time series kept to [`reset`](WindLoading::reset) the iterators,
+    /// see [`raw`](WindLoading::raw): a source feeding several FEM inputs is recovered once and merged sources are dropped.
+    /// The selection is cleared and the time series can be transformed and selected again
+    pub fn into_wind_loads(self) -> WindLoads {
+        let mut loads: Vec<Option<Loads>> = vec![];
+        for x in self.raw.into_iter().flatten() {
+            if !loads.iter().flatten().any(|y| y.name() == x.name()) {
+                loads.push(Some(x));
+            }
+        }
+        WindLoads::from_loads(self.time, loads).with_units(self.units)
+    }
+    /// Flags the wind loads as desynchronized if some but not all of the time series are exhausted
+    fn check_sync(&mut self) {
+        let exhausted = self
+            .loads
+            .iter()
+            .filter(|x| io_len(x).unwrap_or(0) == 0)
+            .count();
+        if !self.desynced && exhausted > 0 && exhausted < self.loads.len() {
+            log::warn!(
+                "{} of {} wind loads time series exhausted before the others",
+                exhausted,
+                self.loads.len()
+            );
+            self.desynced = true;
+        }
+    }
+}
+
+impl fmt::Display for WindLoading {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "wind loading: {} samples", self.n_sample)?;
+        for name in self.source_names() {
+            writeln!(f, " - {}", name)?;
+        }
+        Ok(())
+    }
 }
 
 /// Wind loading interface
@@ -363,10 +2685,35 @@ impl Dos for WindLoading {
     ) -> std::result::Result<&mut Self, DOSIOSError> {
         Err(DOSIOSError::Inputs((WindLoadsError::Inputs).into()))
     }
+    /// Returns the next sample of all the wind loads
+    ///
+    /// Returns `None` once all the time series are exhausted; if some of the time series are exhausted before the others,
+    /// only the samples of the remaining ones are returned and the wind loading is flagged as [`desynced`](WindLoading::desynced)
     fn outputs(&mut self) -> Option<Vec<IO<Self::Output>>> {
-        self.loads
+        self.check_sync();
+        let outputs: Vec<IO<Vec<f64>>> = self
+            .loads
             .iter_mut()
-            .map(|x| -> Option<IO<Vec<f64>>> { x.into() })
-            .collect()
+            .filter_map(|x| -> Option<IO<Vec<f64>>> { x.into() })
+            .collect();
+        if outputs.is_empty() {
+            None
+        } else {
+            Some(outputs)
+        }
+    }
+}
+/// Steps through the wind loads time series
+///
+/// Each iteration returns the next sample of all the wind loads,
+/// the iterator ends when one of the time series is exhausted
+impl Iterator for WindLoading {
+    type Item = Vec<IO<Vec<f64>>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining() == 0 {
+            self.check_sync();
+            return None;
+        }
+        self.outputs()
     }
 }
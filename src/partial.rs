@@ -0,0 +1,241 @@
+//! Partial reading of the wind loads data files
+//!
+//! Only the loads of the requested sources are converted into [`Loads`],
+//! the loads of the other sources are skipped and set to `None`,
+//! and only one time sample every decimation rate samples is kept
+
+use crate::{
+    loads_from_name, open_file, source_name, wind_loads_name, Loads, Result, WindLoads,
+    WindLoadsError, GZIP_MAGIC, PICKLE_READER_CAPACITY,
+};
+use flate2::read::GzDecoder;
+use serde::de::{
+    self, Deserialize, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor,
+};
+use serde_pickle as pkl;
+use std::{
+    fmt,
+    io::{BufRead, BufReader, Read},
+    marker::PhantomData,
+    path::Path,
+    sync::Arc,
+};
+
+impl WindLoads {
+    /// Reads the loads of the wind loads sources `names` from a pickle file
+    ///
+    /// The sources may be given by one of their alternate names,
+    /// the loads of the other sources are skipped and set to `None`,
+    /// gzip compressed pickle files are decompressed on the fly
+    pub fn from_pickle_sources<P: AsRef<Path>>(path: P, names: &[&str]) -> Result<Self> {
+        let names = names
+            .iter()
+            .map(|x| source_name(x).ok_or_else(|| WindLoadsError::UnknownSource(x.to_string())))
+            .collect::<Result<Vec<_>>>()?;
+        read_sources(path, &names, 1)
+    }
+    /// Reads the wind loads from a pickle file keeping only one time sample every `decimation_rate` samples
+    ///
+    /// The time series are decimated while they are deserialized, the full rate loads are never converted into [`Loads`];
+    /// gzip compressed pickle files are decompressed on the fly
+    pub fn from_pickle_decimated<P: AsRef<Path>>(path: P, decimation_rate: usize) -> Result<Self> {
+        assert!(
+            decimation_rate > 0,
+            "decimation_rate must be greater than 0"
+        );
+        read_sources(path, &wind_loads_name(), decimation_rate)
+    }
+}
+
+fn read_sources<P: AsRef<Path>>(path: P, names: &[&str], rate: usize) -> Result<WindLoads> {
+    let mut r = BufReader::with_capacity(PICKLE_READER_CAPACITY, open_file(path)?);
+    if r.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        deserialize(GzDecoder::new(r), names, rate).map_err(|e| match e {
+            pkl::Error::Io(e) => WindLoadsError::Decompression(Arc::new(e)),
+            e => e.into(),
+        })
+    } else {
+        Ok(deserialize(r, names, rate)?)
+    }
+}
+fn deserialize<R: Read>(reader: R, names: &[&str], rate: usize) -> pkl::Result<WindLoads> {
+    let mut de = pkl::Deserializer::new(reader, false);
+    let wind_loads = WindLoadsSeed { names, rate }.deserialize(&mut de)?;
+    de.end()?;
+    Ok(wind_loads)
+}
+
+/// Wind loads deserializer keeping only the sources `names` and one time sample every `rate` samples
+struct WindLoadsSeed<'a> {
+    names: &'a [&'a str],
+    rate: usize,
+}
+impl<'de, 'a> DeserializeSeed<'de> for WindLoadsSeed<'a> {
+    type Value = WindLoads;
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+impl<'de, 'a> Visitor<'de> for WindLoadsSeed<'a> {
+    type Value = WindLoads;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a map with the wind loads outputs, time and units")
+    }
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let (mut loads, mut time, mut units) = (None, None, None);
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "outputs" => {
+                    loads = Some(map.next_value_seed(OutputsSeed {
+                        names: self.names,
+                        rate: self.rate,
+                    })?)
+                }
+                "time" => time = Some(map.next_value_seed(SamplesSeed::new(self.rate))?),
+                "units" => units = Some(map.next_value()?),
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        let loads = loads.ok_or_else(|| de::Error::missing_field("outputs"))?;
+        let time = time.ok_or_else(|| de::Error::missing_field("time"))?;
+        Ok(WindLoads::from_loads(time, loads).with_units(units.unwrap_or_default()))
+    }
+}
+
+/// Wind loads outputs deserializer keeping only the sources `names` and one time sample every `rate` samples
+struct OutputsSeed<'a> {
+    names: &'a [&'a str],
+    rate: usize,
+}
+impl<'de, 'a> DeserializeSeed<'de> for OutputsSeed<'a> {
+    type Value = Vec<Option<Loads>>;
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+impl<'de, 'a> Visitor<'de> for OutputsSeed<'a> {
+    type Value = Vec<Option<Loads>>;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a sequence of wind loads")
+    }
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let mut outputs = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(loads) = seq.next_element_seed(LoadsSeed {
+            names: self.names,
+            rate: self.rate,
+        })? {
+            outputs.push(loads);
+        }
+        Ok(outputs)
+    }
+}
+
+/// Deserializer of the loads of a single source, the loads are `None` if the source is not in `names`
+struct LoadsSeed<'a> {
+    names: &'a [&'a str],
+    rate: usize,
+}
+impl<'de, 'a> DeserializeSeed<'de> for LoadsSeed<'a> {
+    type Value = Option<Loads>;
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Self::Value, D::Error> {
+        deserializer.deserialize_option(self)
+    }
+}
+impl<'de, 'a> Visitor<'de> for LoadsSeed<'a> {
+    type Value = Option<Loads>;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a single entry map of a wind loads source name and its loads, or none")
+    }
+    fn visit_none<E: de::Error>(self) -> std::result::Result<Self::Value, E> {
+        Ok(None)
+    }
+    fn visit_unit<E: de::Error>(self) -> std::result::Result<Self::Value, E> {
+        Ok(None)
+    }
+    fn visit_some<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let key: String = map
+            .next_key()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let name = source_name(&key)
+            .ok_or_else(|| de::Error::custom(format!("unknown wind loads source: {}", key)))?;
+        let loads = if self.names.contains(&name) {
+            loads_from_name(name, map.next_value_seed(SamplesSeed::new(self.rate))?)
+        } else {
+            map.next_value::<IgnoredAny>()?;
+            None
+        };
+        while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+        Ok(loads)
+    }
+}
+
+/// Deserializer of a time series keeping only one sample every `rate` samples
+struct SamplesSeed<T> {
+    rate: usize,
+    sample: PhantomData<T>,
+}
+impl<T> SamplesSeed<T> {
+    fn new(rate: usize) -> Self {
+        Self {
+            rate,
+            sample: PhantomData,
+        }
+    }
+}
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for SamplesSeed<T> {
+    type Value = Vec<T>;
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+impl<'de, T: Deserialize<'de>> Visitor<'de> for SamplesSeed<T> {
+    type Value = Vec<T>;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a sequence of time samples")
+    }
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let n_sample = seq.size_hint().unwrap_or_default();
+        let mut samples = Vec::with_capacity(n_sample.div_ceil(self.rate));
+        while let Some(sample) = seq.next_element()? {
+            samples.push(sample);
+            for _ in 1..self.rate {
+                if seq.next_element::<IgnoredAny>()?.is_none() {
+                    return Ok(samples);
+                }
+            }
+        }
+        Ok(samples)
+    }
+}
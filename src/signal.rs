@@ -0,0 +1,459 @@
+//! Signal processing helpers applied to the time series of a single load component
+
+use rustfft::{num_complex::Complex, FftPlanner};
+use std::f64::consts::PI;
+
+/// Length of the segments of the overlap-add band-pass filter, shorter series are filtered in a single FFT
+const BANDPASS_SEGMENT: usize = 1 << 16;
+
+/// Applies `f` to the time series of each component of a time-major data set
+///
+/// The data is transposed into component time series, processed with `f` and written back
+pub(crate) fn map_components<F>(data: &mut [Vec<f64>], f: F)
+where
+    F: Fn(&[f64]) -> Vec<f64>,
+{
+    let width = data.first().map_or(0, |x| x.len());
+    for k in 0..width {
+        let series: Vec<f64> = data.iter().map(|x| x[k]).collect();
+        f(&series)
+            .into_iter()
+            .zip(data.iter_mut())
+            .for_each(|(y, x)| x[k] = y);
+    }
+}
+
+/// Hamming windowed-sinc low-pass FIR filter taps
+///
+/// `cutoff` is the cutoff frequency normalized to the sampling frequency (0 < `cutoff` < 0.5)
+/// and the taps are scaled to a unit DC gain
+pub(crate) fn fir_lowpass_taps(cutoff: f64, n_taps: usize) -> Vec<f64> {
+    let m = (n_taps - 1) as f64;
+    let taps: Vec<f64> = (0..n_taps)
+        .map(|i| {
+            let x = i as f64 - 0.5 * m;
+            let sinc = if x == 0f64 {
+                2. * cutoff
+            } else {
+                (2. * PI * cutoff * x).sin() / (PI * x)
+            };
+            let window = if n_taps > 1 {
+                0.54 - 0.46 * (2. * PI * i as f64 / m).cos()
+            } else {
+                1f64
+            };
+            sinc * window
+        })
+        .collect();
+    let gain: f64 = taps.iter().sum();
+    taps.into_iter().map(|x| x / gain).collect()
+}
+
+/// Zero-phase filtering of a time series with a symmetric FIR filter
+///
+/// The filter is centered on each sample and the edges of the series are extended with the first and last values
+pub(crate) fn fir_filter_centered(series: &[f64], taps: &[f64]) -> Vec<f64> {
+    let n = series.len() as isize;
+    if n == 0 {
+        return vec![];
+    }
+    let half = (taps.len() / 2) as isize;
+    (0..n)
+        .map(|i| {
+            taps.iter()
+                .enumerate()
+                .map(|(j, t)| {
+                    let k = (i + j as isize - half).clamp(0, n - 1);
+                    t * series[k as usize]
+                })
+                .sum()
+        })
+        .collect()
+}
+
+/// Centered moving average of a time series over `window` samples
+///
+/// The window is truncated at the edges of the series and the average is taken over the remaining samples
+pub(crate) fn moving_average(series: &[f64], window: usize) -> Vec<f64> {
+    let n = series.len();
+    let half = window / 2;
+    let cumsum: Vec<f64> = std::iter::once(0f64)
+        .chain(series.iter().scan(0f64, |s, x| {
+            *s += x;
+            Some(*s)
+        }))
+        .collect();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (i.saturating_sub(half), (i + half + 1).min(n));
+            (cumsum[b] - cumsum[a]) / (b - a) as f64
+        })
+        .collect()
+}
+
+/// Centered sliding median of a time series over `window` samples
+///
+/// The window is shrunk symmetrically at the edges of the series so it stays centered with an odd length
+pub(crate) fn median_filter(series: &[f64], window: usize) -> Vec<f64> {
+    let n = series.len();
+    let half = window / 2;
+    (0..n)
+        .map(|i| {
+            let h = half.min(i).min(n - 1 - i);
+            let mut samples = series[i - h..=i + h].to_vec();
+            samples.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            samples[samples.len() / 2]
+        })
+        .collect()
+}
+
+/// Centered sliding minimum and maximum of a time series over `window` samples
+///
+/// The window is truncated at the edges of the series and the extrema are taken over the remaining samples
+pub(crate) fn envelope(series: &[f64], window: usize) -> Vec<(f64, f64)> {
+    let n = series.len();
+    let half = window / 2;
+    (0..n)
+        .map(|i| {
+            series[i.saturating_sub(half)..(i + half + 1).min(n)]
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), x| {
+                    (min.min(*x), max.max(*x))
+                })
+        })
+        .collect()
+}
+
+/// Returns the turning points of a time series, including the first and the last samples
+fn reversals(series: &[f64]) -> Vec<f64> {
+    let mut points: Vec<f64> = Vec::with_capacity(series.len());
+    for &x in series {
+        match points.as_slice() {
+            [.., a, b] if (b - a) * (x - b) >= 0f64 => {
+                *points.last_mut().unwrap() = x;
+            }
+            [b] if *b == x => (),
+            _ => points.push(x),
+        }
+    }
+    points
+}
+
+/// Rainflow cycle counting of a time series following the ASTM E1049 standard
+///
+/// Returns the (range, mean, count) of each cycle, the count is 1 for full cycles and 0.5 for half cycles
+pub(crate) fn rainflow(series: &[f64]) -> Vec<(f64, f64, f64)> {
+    let cycle = |a: f64, b: f64, count: f64| ((a - b).abs(), 0.5 * (a + b), count);
+    let mut cycles = vec![];
+    let mut stack: Vec<f64> = Vec::new();
+    for x in reversals(series) {
+        stack.push(x);
+        while let [.., c, b, a] = stack[..] {
+            let (range_x, range_y) = ((a - b).abs(), (b - c).abs());
+            if range_x < range_y {
+                break;
+            }
+            if stack.len() == 3 {
+                cycles.push(cycle(c, b, 0.5));
+                stack.remove(0);
+            } else {
+                cycles.push(cycle(c, b, 1.));
+                let n = stack.len();
+                stack.drain(n - 3..n - 1);
+            }
+        }
+    }
+    cycles.extend(stack.windows(2).map(|x| cycle(x[0], x[1], 0.5)));
+    cycles
+}
+
+/// Replaces the spikes of a time series by linear interpolation of the neighboring samples
+///
+/// A sample is a spike if it departs from the median of the `2*half_window+1` samples centered on it
+/// by more than `n_sigma` standard deviations of the series
+pub(crate) fn despike(series: &[f64], n_sigma: f64, half_window: usize) -> Vec<f64> {
+    let n = series.len();
+    if n < 2 {
+        return series.to_vec();
+    }
+    let mean = series.iter().sum::<f64>() / n as f64;
+    let std = (series.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64).sqrt();
+    let is_spike: Vec<bool> = median_filter(series, 2 * half_window + 1)
+        .into_iter()
+        .zip(series)
+        .map(|(m, x)| (x - m).abs() > n_sigma * std)
+        .collect();
+    (0..n)
+        .map(|i| {
+            if !is_spike[i] {
+                return series[i];
+            }
+            let previous = (0..i).rev().find(|&j| !is_spike[j]);
+            let next = (i + 1..n).find(|&j| !is_spike[j]);
+            match (previous, next) {
+                (Some(a), Some(b)) => {
+                    let w = (i - a) as f64 / (b - a) as f64;
+                    series[a] + w * (series[b] - series[a])
+                }
+                (Some(a), None) => series[a],
+                (None, Some(b)) => series[b],
+                (None, None) => series[i],
+            }
+        })
+        .collect()
+}
+
+/// Linear interpolation of a time-major data set at the fractional sample `positions`
+///
+/// Positions beyond the last sample are clamped to the last sample
+pub(crate) fn interpolate_samples(data: &[Vec<f64>], positions: &[f64]) -> Vec<Vec<f64>> {
+    let n = data.len();
+    positions
+        .iter()
+        .map(|&p| {
+            let i = p.floor().max(0f64) as usize;
+            if i + 1 >= n {
+                return data[n - 1].clone();
+            }
+            let w = p - i as f64;
+            if w == 0f64 {
+                return data[i].clone();
+            }
+            data[i]
+                .iter()
+                .zip(data[i + 1].iter())
+                .map(|(a, b)| a + w * (b - a))
+                .collect()
+        })
+        .collect()
+}
+
+/// IIR filter second-order section with normalized denominator (`a[0]=1`)
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Biquad {
+    b: [f64; 3],
+    a: [f64; 3],
+}
+impl Biquad {
+    /// Static gain of the section
+    fn dc_gain(&self) -> f64 {
+        self.b.iter().sum::<f64>() / self.a.iter().sum::<f64>()
+    }
+    /// Filters the series with the section states initialized to the steady state of the first sample
+    fn filter(&self, series: &[f64]) -> Vec<f64> {
+        let Biquad { b, a } = self;
+        let x0 = series.first().cloned().unwrap_or_default();
+        let y0 = self.dc_gain() * x0;
+        let mut z2 = (b[2] - a[2] * self.dc_gain()) * x0;
+        let mut z1 = (b[1] * x0 - a[1] * y0) + z2;
+        series
+            .iter()
+            .map(|&x| {
+                let y = b[0] * x + z1;
+                z1 = b[1] * x - a[1] * y + z2;
+                z2 = b[2] * x - a[2] * y;
+                y
+            })
+            .collect()
+    }
+}
+
+/// Butterworth low-pass IIR filter as a cascade of second-order sections
+///
+/// `cutoff` is the cutoff frequency normalized to the sampling frequency (0 < `cutoff` < 0.5);
+/// the analog prototype is discretized with the bilinear transform with frequency pre-warping
+pub(crate) fn butterworth_lowpass(order: usize, cutoff: f64) -> Vec<Biquad> {
+    let w0 = 2. * PI * cutoff;
+    let (sin_w0, cos_w0) = w0.sin_cos();
+    let mut sections: Vec<Biquad> = (1..=order / 2)
+        .map(|k| {
+            let q = 0.5 / ((2 * k - 1 + order % 2) as f64 * PI / (2 * order) as f64).cos();
+            let alpha = 0.5 * sin_w0 / q;
+            let a0 = 1. + alpha;
+            let b0 = 0.5 * (1. - cos_w0) / a0;
+            Biquad {
+                b: [b0, 2. * b0, b0],
+                a: [1., -2. * cos_w0 / a0, (1. - alpha) / a0],
+            }
+        })
+        .collect();
+    if order % 2 == 1 {
+        let k = (0.5 * w0).tan();
+        let b0 = k / (1. + k);
+        sections.push(Biquad {
+            b: [b0, b0, 0.],
+            a: [1., (k - 1.) / (k + 1.), 0.],
+        });
+    }
+    sections
+}
+
+/// Filters a time series with a cascade of second-order sections
+pub(crate) fn sos_filter(sections: &[Biquad], series: &[f64]) -> Vec<f64> {
+    sections
+        .iter()
+        .fold(series.to_vec(), |x, section| section.filter(&x))
+}
+
+/// Zero-phase filtering of a time series with a cascade of second-order sections
+///
+/// The series is filtered forward and backward after being extended at both ends
+/// by odd reflection to reduce the edge transients
+pub(crate) fn sos_filtfilt(sections: &[Biquad], series: &[f64]) -> Vec<f64> {
+    let n = series.len();
+    if n < 2 {
+        return series.to_vec();
+    }
+    let n_pad = (3 * (2 * sections.len() + 1)).min(n - 1);
+    let (first, last) = (series[0], series[n - 1]);
+    let padded: Vec<f64> = (1..=n_pad)
+        .rev()
+        .map(|i| 2. * first - series[i])
+        .chain(series.iter().cloned())
+        .chain((1..=n_pad).map(|i| 2. * last - series[n - 1 - i]))
+        .collect();
+    let mut y = sos_filter(sections, &padded);
+    y.reverse();
+    let mut y = sos_filter(sections, &y);
+    y.reverse();
+    y[n_pad..n_pad + n].to_vec()
+}
+
+/// Normalized cross-correlation of two time series for lags in `-max_lag..=max_lag`
+///
+/// The means of the series are removed and the correlation at lag `l` is the sum of `x[n]*y[n+l]`
+/// normalized by the square root of the product of the energies of `x` and `y`
+pub(crate) fn xcorr(x: &[f64], y: &[f64], max_lag: usize) -> Vec<(isize, f64)> {
+    let n = x.len().min(y.len());
+    let centered = |s: &[f64]| -> Vec<f64> {
+        let mean = s[..n].iter().sum::<f64>() / n as f64;
+        s[..n].iter().map(|s| s - mean).collect()
+    };
+    let (x, y) = (centered(x), centered(y));
+    let norm = (x.iter().map(|x| x * x).sum::<f64>() * y.iter().map(|y| y * y).sum::<f64>()).sqrt();
+    let max_lag = max_lag.min(n.saturating_sub(1)) as isize;
+    (-max_lag..=max_lag)
+        .map(|lag| {
+            let (x, y) = if lag < 0 {
+                (&x[(-lag) as usize..], &y[..])
+            } else {
+                (&x[..], &y[lag as usize..])
+            };
+            let r: f64 = x.iter().zip(y).map(|(x, y)| x * y).sum();
+            (lag, if norm > 0f64 { r / norm } else { 0f64 })
+        })
+        .collect()
+}
+
+/// Band-pass filtering of a time series by zeroing the FFT bins outside [`low`,`high`]
+///
+/// `low` and `high` are normalized by the sampling frequency. Series longer than [`BANDPASS_SEGMENT`] are split into
+/// Hann windowed segments with 50% overlap that are filtered separately and overlap-added
+pub(crate) fn bandpass(series: &[f64], low: f64, high: f64) -> Vec<f64> {
+    let n = series.len();
+    let nfft = n.min(BANDPASS_SEGMENT);
+    if nfft < 2 {
+        return series.to_vec();
+    }
+    let mut planner = FftPlanner::new();
+    let (fft, ifft) = (
+        planner.plan_fft_forward(nfft),
+        planner.plan_fft_inverse(nfft),
+    );
+    let filter = |buffer: &mut Vec<Complex<f64>>| {
+        fft.process(buffer);
+        buffer.iter_mut().enumerate().for_each(|(k, x)| {
+            let f = k.min(nfft - k) as f64 / nfft as f64;
+            if f < low || f > high {
+                *x = Complex::new(0f64, 0f64);
+            }
+        });
+        ifft.process(buffer);
+    };
+    if n == nfft {
+        let mut buffer: Vec<Complex<f64>> = series.iter().map(|x| Complex::new(*x, 0f64)).collect();
+        filter(&mut buffer);
+        return buffer.iter().map(|x| x.re / nfft as f64).collect();
+    }
+    let hop = nfft / 2;
+    let window: Vec<f64> = (0..nfft)
+        .map(|i| 0.5 - 0.5 * (2. * PI * i as f64 / nfft as f64).cos())
+        .collect();
+    let n_segment = n.div_ceil(hop) + 1;
+    let padded: Vec<f64> = std::iter::repeat_n(0f64, hop)
+        .chain(series.iter().cloned())
+        .chain(std::iter::repeat(0f64))
+        .take((n_segment + 1) * hop)
+        .collect();
+    let mut filtered = vec![0f64; padded.len()];
+    for i in (0..n_segment).map(|k| k * hop) {
+        let mut buffer: Vec<Complex<f64>> = padded[i..i + nfft]
+            .iter()
+            .zip(&window)
+            .map(|(x, w)| Complex::new(x * w, 0f64))
+            .collect();
+        filter(&mut buffer);
+        filtered[i..i + nfft]
+            .iter_mut()
+            .zip(&buffer)
+            .for_each(|(y, x)| *y += x.re / nfft as f64);
+    }
+    filtered[hop..hop + n].to_vec()
+}
+
+/// Cross power spectral density of two time series with Welch's method
+///
+/// The series are split into segments of `nfft` samples with 50% overlap, the mean of each segment
+/// is removed and a Hann window is applied. Returns the one-sided cross power spectral density
+/// at the frequencies `k*sampling_hz/nfft` for `k` in `0..=nfft/2`
+pub(crate) fn welch_csd(
+    x: &[f64],
+    y: &[f64],
+    sampling_hz: f64,
+    nfft: usize,
+) -> Vec<(f64, Complex<f64>)> {
+    let n = x.len().min(y.len());
+    assert!(nfft > 1, "nfft must be greater than 1");
+    assert!(
+        nfft <= n,
+        "nfft cannot be greater than the number of sample ({})",
+        n
+    );
+    let window: Vec<f64> = (0..nfft)
+        .map(|i| 0.5 - 0.5 * (2. * PI * i as f64 / nfft as f64).cos())
+        .collect();
+    let scale = 1. / (sampling_hz * window.iter().map(|w| w * w).sum::<f64>());
+    let fft = FftPlanner::new().plan_fft_forward(nfft);
+    let spectrum = |s: &[f64]| -> Vec<Complex<f64>> {
+        let mean = s.iter().sum::<f64>() / nfft as f64;
+        let mut buffer: Vec<Complex<f64>> = s
+            .iter()
+            .zip(&window)
+            .map(|(s, w)| Complex::new((s - mean) * w, 0f64))
+            .collect();
+        fft.process(&mut buffer);
+        buffer
+    };
+    let n_freq = nfft / 2 + 1;
+    let step = nfft / 2;
+    let starts: Vec<usize> = (0..=n - nfft).step_by(step).collect();
+    let mut csd = vec![Complex::new(0f64, 0f64); n_freq];
+    for &i in &starts {
+        let fx = spectrum(&x[i..i + nfft]);
+        let fy = spectrum(&y[i..i + nfft]);
+        csd.iter_mut()
+            .zip(fx.iter().zip(&fy))
+            .for_each(|(c, (fx, fy))| *c += fx.conj() * fy);
+    }
+    let n_segment = starts.len() as f64;
+    csd.into_iter()
+        .enumerate()
+        .map(|(k, c)| {
+            let one_sided = if k == 0 || 2 * k == nfft { 1f64 } else { 2f64 };
+            (
+                k as f64 * sampling_hz / nfft as f64,
+                c * one_sided * scale / n_segment,
+            )
+        })
+        .collect()
+}
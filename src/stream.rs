@@ -0,0 +1,184 @@
+//! Sample-major wind loads file format
+//!
+//! The file starts with a header:
+//!  - the magic bytes `WLST`,
+//!  - the number of samples (u64),
+//!  - the number of sources (u32),
+//!  - and for each source, the length of its name (u32), the name (UTF-8) and the sample length (u32),
+//!
+//! followed by one record per time sample made of the time (f64)
+//! and of the sample of each source (f64), all numbers are little-endian.
+//! The header is checked against the file size when the stream is opened.
+
+use crate::{create_file, io_from_name, open_file, Result, WindLoads, WindLoadsError};
+use dosio::IO;
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Seek, Write},
+    path::Path,
+    sync::Arc,
+};
+
+const MAGIC: &[u8; 4] = b"WLST";
+
+impl WindLoads {
+    /// Writes the wind loads to a sample-major file that can be read with [`WindLoadsStream`]
+    pub fn to_streamable<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let sources: Vec<_> = self.loads.iter().filter_map(|x| x.as_ref()).collect();
+        let n_sample = self.len()?;
+        if let Some(x) = sources.iter().find(|x| x.len() != n_sample) {
+            return Err(WindLoadsError::Mismatch {
+                expected: n_sample,
+                got: x.len(),
+                source: x.name().to_string(),
+            });
+        }
+        if self.time.len() < n_sample {
+            return Err(WindLoadsError::Mismatch {
+                expected: n_sample,
+                got: self.time.len(),
+                source: "time".to_string(),
+            });
+        }
+        let mut w = BufWriter::new(create_file(path)?);
+        w.write_all(MAGIC)?;
+        w.write_all(&(n_sample as u64).to_le_bytes())?;
+        w.write_all(&(sources.len() as u32).to_le_bytes())?;
+        for x in &sources {
+            let name = x.name().as_bytes();
+            w.write_all(&(name.len() as u32).to_le_bytes())?;
+            w.write_all(name)?;
+            let width = x.as_slice().first().map_or(0, |x| x.len());
+            w.write_all(&(width as u32).to_le_bytes())?;
+        }
+        for (i, t) in self.time.iter().take(n_sample).enumerate() {
+            w.write_all(&t.to_le_bytes())?;
+            for x in &sources {
+                for v in &x.as_slice()[i] {
+                    w.write_all(&v.to_le_bytes())?;
+                }
+            }
+        }
+        w.flush()?;
+        Ok(())
+    }
+}
+
+/// Wind loads stream
+///
+/// Reads the wind loads one time sample at a time from a file written with [`WindLoads::to_streamable`],
+/// the memory footprint is independent of the duration of the time series.
+/// The stream yields an error if a sample cannot be read and ends after it
+pub struct WindLoadsStream {
+    reader: BufReader<File>,
+    sources: Vec<(String, usize)>,
+    n_sample: usize,
+    step: usize,
+    time: Option<f64>,
+}
+impl WindLoadsStream {
+    /// Opens a wind loads stream file
+    ///
+    /// Returns [`WindLoadsError::InvalidStream`] if the header does not match the size of the file
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = open_file(path)?;
+        let file_len = file.metadata().map_err(stream_error)?.len();
+        let mut reader = BufReader::new(file);
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic).map_err(stream_error)?;
+        if &magic != MAGIC {
+            return Err(WindLoadsError::InvalidStream);
+        }
+        let n_sample = read_u64(&mut reader)?;
+        let n_source = read_u32(&mut reader)?;
+        let mut sources = vec![];
+        for _ in 0..n_source {
+            let name_len = read_u32(&mut reader)? as u64;
+            if name_len > file_len.saturating_sub(reader.stream_position().map_err(stream_error)?) {
+                return Err(WindLoadsError::InvalidStream);
+            }
+            let mut name = vec![0u8; name_len as usize];
+            reader.read_exact(&mut name).map_err(stream_error)?;
+            let name = String::from_utf8_lossy(&name).into_owned();
+            let width = read_u32(&mut reader)? as usize;
+            sources.push((name, width));
+        }
+        let record_len = sources
+            .iter()
+            .try_fold(1u64, |n, (_, width)| n.checked_add(*width as u64))
+            .and_then(|n| n.checked_mul(8));
+        let data_len = file_len.saturating_sub(reader.stream_position().map_err(stream_error)?);
+        if record_len.and_then(|n| n.checked_mul(n_sample)) != Some(data_len) {
+            return Err(WindLoadsError::InvalidStream);
+        }
+        let n_sample = n_sample as usize;
+        Ok(Self {
+            reader,
+            sources,
+            n_sample,
+            step: 0,
+            time: None,
+        })
+    }
+    /// Returns the number of time samples in the stream
+    pub fn n_sample(&self) -> usize {
+        self.n_sample
+    }
+    /// Returns the names of the wind loads sources in the stream
+    pub fn sources(&self) -> Vec<&str> {
+        self.sources.iter().map(|(name, _)| name.as_str()).collect()
+    }
+    /// Returns the time of the last sample read from the stream
+    pub fn time(&self) -> Option<f64> {
+        self.time
+    }
+    fn read_sample(&mut self) -> Result<Vec<IO<Vec<f64>>>> {
+        self.time = Some(read_f64(&mut self.reader)?);
+        let Self {
+            reader, sources, ..
+        } = self;
+        sources
+            .iter()
+            .map(|(name, width)| {
+                let sample = (0..*width)
+                    .map(|_| read_f64(reader))
+                    .collect::<Result<Vec<f64>>>()?;
+                io_from_name(name, sample)
+                    .ok_or_else(|| WindLoadsError::UnknownSource(name.clone()))
+            })
+            .collect()
+    }
+}
+impl Iterator for WindLoadsStream {
+    type Item = Result<Vec<IO<Vec<f64>>>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.step == self.n_sample {
+            return None;
+        }
+        self.step += 1;
+        let sample = self.read_sample();
+        if sample.is_err() {
+            self.step = self.n_sample;
+        }
+        Some(sample)
+    }
+}
+
+fn stream_error(e: io::Error) -> WindLoadsError {
+    WindLoadsError::StreamRead(Arc::new(e))
+}
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes).map_err(stream_error)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes).map_err(stream_error)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+fn read_f64<R: Read>(reader: &mut R) -> Result<f64> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes).map_err(stream_error)?;
+    Ok(f64::from_le_bytes(bytes))
+}
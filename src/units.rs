@@ -0,0 +1,171 @@
+//! Units of the wind loads
+//!
+//! The units of the forces and of the moments are recorded with the wind loads
+//! and saved in the pickle and JSON files as strings, the data files without units are assumed to be in SI units
+
+use crate::{Loads, WindLoads, WindLoadsError};
+use serde::{Deserialize, Serialize};
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+/// Force unit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ForceUnit {
+    /// Newton
+    #[default]
+    Newton,
+    /// kilo-Newton
+    KiloNewton,
+}
+impl ForceUnit {
+    /// Returns the value of the unit in Newton
+    pub fn to_si(&self) -> f64 {
+        match self {
+            ForceUnit::Newton => 1.,
+            ForceUnit::KiloNewton => 1e3,
+        }
+    }
+}
+impl fmt::Display for ForceUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForceUnit::Newton => write!(f, "N"),
+            ForceUnit::KiloNewton => write!(f, "kN"),
+        }
+    }
+}
+impl FromStr for ForceUnit {
+    type Err = WindLoadsError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "N" => Ok(ForceUnit::Newton),
+            "kN" => Ok(ForceUnit::KiloNewton),
+            _ => Err(WindLoadsError::UnknownUnit(s.to_string())),
+        }
+    }
+}
+impl TryFrom<String> for ForceUnit {
+    type Error = WindLoadsError;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+impl From<ForceUnit> for String {
+    fn from(unit: ForceUnit) -> Self {
+        unit.to_string()
+    }
+}
+
+/// Moment unit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum MomentUnit {
+    /// Newton meter
+    #[default]
+    NewtonMeter,
+    /// Newton millimeter
+    NewtonMillimeter,
+    /// kilo-Newton meter
+    KiloNewtonMeter,
+}
+impl MomentUnit {
+    /// Returns the value of the unit in Newton meter
+    pub fn to_si(&self) -> f64 {
+        match self {
+            MomentUnit::NewtonMeter => 1.,
+            MomentUnit::NewtonMillimeter => 1e-3,
+            MomentUnit::KiloNewtonMeter => 1e3,
+        }
+    }
+}
+impl fmt::Display for MomentUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MomentUnit::NewtonMeter => write!(f, "N.m"),
+            MomentUnit::NewtonMillimeter => write!(f, "N.mm"),
+            MomentUnit::KiloNewtonMeter => write!(f, "kN.m"),
+        }
+    }
+}
+impl FromStr for MomentUnit {
+    type Err = WindLoadsError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "N.m" => Ok(MomentUnit::NewtonMeter),
+            "N.mm" => Ok(MomentUnit::NewtonMillimeter),
+            "kN.m" => Ok(MomentUnit::KiloNewtonMeter),
+            _ => Err(WindLoadsError::UnknownUnit(s.to_string())),
+        }
+    }
+}
+impl TryFrom<String> for MomentUnit {
+    type Error = WindLoadsError;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+impl From<MomentUnit> for String {
+    fn from(unit: MomentUnit) -> Self {
+        unit.to_string()
+    }
+}
+
+/// Units of the forces and of the moments, SI units by default
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Units {
+    pub force: ForceUnit,
+    pub moment: MomentUnit,
+}
+impl Units {
+    /// Creates the units from the force and moment units
+    pub fn new(force: ForceUnit, moment: MomentUnit) -> Self {
+        Self { force, moment }
+    }
+}
+impl fmt::Display for Units {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}]", self.force, self.moment)
+    }
+}
+
+impl Loads {
+    /// Scales the forces by `force_scale` and the moments by `moment_scale`
+    ///
+    /// Each sample is assumed to be made of the forces `[Fx,Fy,Fz]` followed by the moments `[Mx,My,Mz]` of each body
+    fn scale_wrenches(&mut self, force_scale: f64, moment_scale: f64) {
+        for sample in self.io_mut() {
+            for wrench in sample.chunks_mut(6) {
+                let (force, moment) = wrench.split_at_mut(wrench.len().min(3));
+                force.iter_mut().for_each(|x| *x *= force_scale);
+                moment.iter_mut().for_each(|x| *x *= moment_scale);
+            }
+        }
+    }
+}
+
+impl WindLoads {
+    /// Returns the units of the forces and of the moments
+    pub fn units(&self) -> Units {
+        self.units
+    }
+    /// Sets the units of the forces and of the moments without converting the loads
+    ///
+    /// Use it to record the units of a data file written without units
+    pub fn with_units(self, units: Units) -> Self {
+        Self { units, ..self }
+    }
+    /// Converts the forces and the moments of all the sources to the units `to`
+    pub fn convert_units(mut self, to: Units) -> Self {
+        if self.units == to {
+            return self;
+        }
+        let force_scale = self.units.force.to_si() / to.force.to_si();
+        let moment_scale = self.units.moment.to_si() / to.moment.to_si();
+        self.loads
+            .iter_mut()
+            .flatten()
+            .for_each(|x| x.scale_wrenches(force_scale, moment_scale));
+        self.units = to;
+        self
+    }
+}
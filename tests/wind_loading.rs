@@ -0,0 +1,587 @@
+use dosio::{Dos, IO};
+use windloading::{Loads, WindLoading, WindLoads, WindLoadsError};
+
+fn wind_loads(n: usize) -> WindLoads {
+    let time: Vec<f64> = (0..n).map(|i| i as f64 * 0.05).collect();
+    let topend = (0..n).map(|i| vec![i as f64; 6]).collect();
+    let truss = (0..n).map(|i| vec![-(i as f64); 18]).collect();
+    WindLoads::from_loads(
+        time,
+        vec![
+            Some(Loads::OSSTopEnd6F(topend)),
+            None,
+            Some(Loads::OSSTruss6F(truss)),
+        ],
+    )
+}
+
+fn build(n: usize, n_sample: usize) -> WindLoading {
+    wind_loads(n)
+        .n_sample(n_sample)
+        .unwrap()
+        .truss()
+        .unwrap()
+        .topend()
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn outputs_follow_the_selected_sources() {
+    let n_sample = 5;
+    let mut wind = build(8, n_sample);
+    for i in 0..n_sample {
+        let outputs = wind.outputs().unwrap();
+        assert_eq!(outputs.len(), 2);
+        match &outputs[0] {
+            IO::OSSTruss6F { data: Some(data) } => assert_eq!(data, &vec![-(i as f64); 18]),
+            _ => panic!("expected the truss loads first"),
+        }
+        match &outputs[1] {
+            IO::OSSTopEnd6F { data: Some(data) } => assert_eq!(data, &vec![i as f64; 6]),
+            _ => panic!("expected the top-end loads second"),
+        }
+    }
+    assert!(wind.outputs().is_none());
+    assert!(!wind.desynced());
+}
+
+#[test]
+fn source_is_found_by_its_source_name() {
+    let wind = build(4, 4);
+    for name in wind.source_names() {
+        assert!(wind.source(&name).is_some());
+    }
+    assert!(wind.source("OSSTruss6F").is_some());
+}
+
+#[test]
+fn reset_replays_the_time_series() {
+    let n_sample = 4;
+    let mut wind = build(4, n_sample);
+    assert_eq!(wind.by_ref().count(), n_sample);
+    assert_eq!(wind.remaining(), 0);
+    wind.reset();
+    assert_eq!(wind.remaining(), n_sample);
+    assert_eq!(wind.count(), n_sample);
+}
+
+#[test]
+fn short_time_series_are_rejected() {
+    let mut loads = wind_loads(5);
+    if let Some(Loads::OSSTruss6F(data)) = loads.loads[2].as_mut() {
+        data.truncate(3);
+    }
+    let loads = loads.n_sample(5).unwrap();
+    assert!(loads.truss().is_err());
+}
+
+#[test]
+fn unselected_sources_do_not_set_the_number_of_samples() {
+    let mut loads = wind_loads(10);
+    if let Some(Loads::OSSTruss6F(data)) = loads.loads[2].as_mut() {
+        data.truncate(8);
+    }
+    let wind = loads.truss().unwrap().build().unwrap();
+    assert_eq!(wind.n_sample, 8);
+}
+
+#[test]
+fn short_selected_channel_is_rejected_by_build() {
+    let mut loads = wind_loads(10);
+    if let Some(Loads::OSSTruss6F(data)) = loads.loads[2].as_mut() {
+        data.truncate(8);
+    }
+    let err = loads.topend().unwrap().truss().unwrap().build().err();
+    assert!(matches!(
+        err,
+        Some(WindLoadsError::Mismatch {
+            expected: 10,
+            got: 8,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn selected_source_feeds_another_fem_input() {
+    let mut wind = wind_loads(4)
+        .topend()
+        .unwrap()
+        .m2_asm_topend()
+        .unwrap()
+        .build()
+        .unwrap();
+    let outputs = wind.outputs().unwrap();
+    assert_eq!(outputs.len(), 2);
+    match &outputs[1] {
+        IO::MCM2TE6F { data: Some(data) } => assert_eq!(data, &vec![0f64; 6]),
+        _ => panic!("expected the M2 ASM top-end loads second"),
+    }
+}
+
+#[test]
+fn raw_loads_are_kept_for_the_wind_loads_sources() {
+    let mut wind = wind_loads(4)
+        .topend()
+        .unwrap()
+        .m2_asm_topend()
+        .unwrap()
+        .build()
+        .unwrap();
+    assert!(matches!(
+        wind.raw(),
+        [Some(Loads::OSSTopEnd6F(_)), Some(Loads::OSSTopEnd6F(_))]
+    ));
+    assert_eq!(wind.by_ref().count(), 4);
+    wind.reset();
+    let outputs = wind.outputs().unwrap();
+    match (&outputs[0], &outputs[1]) {
+        (IO::OSSTopEnd6F { data: Some(a) }, IO::MCM2TE6F { data: Some(b) }) => {
+            assert_eq!(a, &vec![0f64; 6]);
+            assert_eq!(b, &vec![0f64; 6]);
+        }
+        _ => panic!("expected the top-end and M2 ASM top-end loads"),
+    }
+}
+
+#[test]
+fn wind_loading_gives_back_the_loads_of_each_source() {
+    let wind = wind_loads(4)
+        .select_into("OSS_TopEnd_6F", dosio::io::jar::OSSTruss6F::io())
+        .unwrap()
+        .m2_asm_topend()
+        .unwrap()
+        .truss()
+        .unwrap()
+        .build()
+        .unwrap();
+    let loads = wind.into_wind_loads();
+    assert_eq!(
+        loads.available_sources(),
+        vec!["OSS_TopEnd_6F", "OSS_Truss_6F"]
+    );
+    match &loads.loads[..] {
+        [Some(Loads::OSSTopEnd6F(topend)), Some(Loads::OSSTruss6F(truss))] => {
+            assert_eq!(topend[3], vec![3f64; 6]);
+            assert_eq!(truss[3], vec![-3f64; 18]);
+        }
+        _ => panic!("expected the top-end and the truss loads"),
+    }
+}
+
+#[test]
+fn retagged_source_can_be_selected_again() {
+    let loads = wind_loads(4).m2_asm_topend().unwrap().topend().unwrap();
+    assert_eq!(loads.selected_tags().len(), 2);
+}
+
+#[test]
+fn merged_source_cannot_be_selected_again() {
+    let err = wind_loads(4)
+        .merge_sources(
+            "OSS_TopEnd_6F",
+            "OSS_Truss_6F",
+            dosio::io::jar::OSSM1Lcl::io(),
+        )
+        .unwrap()
+        .topend()
+        .err();
+    assert!(matches!(err, Some(WindLoadsError::AlreadySelected(_))));
+}
+
+#[test]
+fn deselected_source_is_put_back() {
+    let loads = wind_loads(4)
+        .m2_asm_topend()
+        .unwrap()
+        .topend()
+        .unwrap()
+        .truss()
+        .unwrap()
+        .deselect("OSS_TopEnd_6F")
+        .unwrap();
+    assert_eq!(loads.selected_tags().len(), 1);
+    assert_eq!(loads.available_sources(), vec!["OSS_TopEnd_6F"]);
+    let loads = loads.topend().unwrap();
+    assert_eq!(loads.selected_tags().len(), 2);
+    assert!(matches!(
+        wind_loads(4).deselect("OSS_mirrorCovers_6F").err(),
+        Some(WindLoadsError::UnknownSource(_))
+    ));
+}
+
+#[test]
+fn window_past_the_end_is_rejected() {
+    let loads = wind_loads(5);
+    assert!(matches!(
+        loads.window(100., 200.).err(),
+        Some(WindLoadsError::Empty)
+    ));
+    assert_eq!(loads.window(0.1, 0.2).unwrap().time.len(), 2);
+}
+
+#[test]
+fn range_past_the_end_is_empty() {
+    let loads = wind_loads(5).range(100., 200.);
+    assert!(loads.time.is_empty());
+    assert!(loads.loads.iter().flatten().all(|x| x.is_empty()));
+    assert!(wind_loads(5).range(0.2, 0.1).time.is_empty());
+}
+
+#[test]
+fn coherence_rejects_invalid_arguments() {
+    let loads = wind_loads(5);
+    assert!(matches!(
+        loads.coherence("OSS_TopEnd_6F", 0, "OSS_Truss_6F", 0, 20., 16),
+        Err(WindLoadsError::Nfft { .. })
+    ));
+    assert!(matches!(
+        loads.coherence("OSS_TopEnd_6F", 6, "OSS_Truss_6F", 0, 20., 4),
+        Err(WindLoadsError::Component { .. })
+    ));
+    assert!(loads
+        .coherence("OSS_TopEnd_6F", 0, "OSS_Truss_6F", 0, 20., 4)
+        .is_ok());
+}
+
+#[test]
+fn xcorr_rejects_out_of_range_components() {
+    let loads = wind_loads(5);
+    assert!(matches!(
+        loads.xcorr("OSS_TopEnd_6F", 7, "OSS_Truss_6F", 0, 2),
+        Err(WindLoadsError::Component { component: 7, .. })
+    ));
+    assert!(matches!(
+        loads.xcorr("OSS_TopEnd_6F", 0, "OSS_Truss_6F", 18, 2),
+        Err(WindLoadsError::Component { component: 18, .. })
+    ));
+}
+
+#[test]
+fn resample_preserves_the_first_and_last_samples() {
+    let time: Vec<f64> = (0..5).map(|i| i as f64 * 0.1).collect();
+    let topend = (0..5).map(|i| vec![i as f64; 6]).collect();
+    let loads = WindLoads::from_loads(time, vec![Some(Loads::OSSTopEnd6F(topend))])
+        .resample(10., 5.)
+        .unwrap();
+    assert_eq!(loads.time.len(), 3);
+    assert!((loads.time[2] - 0.4).abs() < 1e-12);
+    match &loads.loads[0] {
+        Some(Loads::OSSTopEnd6F(data)) => {
+            assert_eq!(data.len(), 3);
+            assert_eq!(data[0], vec![0f64; 6]);
+            assert_eq!(data[2], vec![4f64; 6]);
+        }
+        _ => panic!("expected the top-end loads"),
+    }
+}
+
+#[test]
+fn resample_keeps_a_uniform_time_grid() {
+    let loads = wind_loads(100).resample(20., 3.).unwrap();
+    assert_eq!(loads.time.len(), 15);
+    assert!((loads.time[14] - 14. / 3.).abs() < 1e-12);
+    assert!((loads.sampling_frequency().unwrap() - 3.).abs() < 1e-9);
+    assert!(loads.resample(3., 1.).is_ok());
+}
+
+#[test]
+fn resample_rejects_empty_loads() {
+    let loads = WindLoads::from_loads(vec![0., 0.1], vec![Some(Loads::OSSTopEnd6F(vec![]))]);
+    assert!(matches!(
+        loads.resample(10., 5.).err(),
+        Some(WindLoadsError::Empty)
+    ));
+}
+
+fn contents(loads: &WindLoads) -> Vec<Option<(String, Vec<Vec<f64>>)>> {
+    loads
+        .loads
+        .iter()
+        .map(|x| {
+            x.as_ref()
+                .map(|x| (x.name().to_string(), x.io_ref().clone()))
+        })
+        .collect()
+}
+
+#[test]
+fn pickle_round_trip_keeps_the_missing_sources() {
+    let path =
+        std::env::temp_dir().join(format!("windloading-round-trip-{}.pkl", std::process::id()));
+    let loads = wind_loads(4);
+    loads.to_pickle(&path).unwrap();
+    let first = WindLoads::from_pickle(&path).unwrap();
+    first.to_pickle(&path).unwrap();
+    let second = WindLoads::from_pickle(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(first.loads[1].is_none());
+    assert_eq!(contents(&first), contents(&loads));
+    assert_eq!(contents(&second), contents(&first));
+    assert_eq!(second.time, loads.time);
+}
+
+#[test]
+fn compact_pickle_round_trip() {
+    use windloading::{ForceUnit, MomentUnit, Units};
+    let path = std::env::temp_dir().join(format!("windloading-compact-{}.pkl", std::process::id()));
+    let units = Units::new(ForceUnit::KiloNewton, MomentUnit::KiloNewtonMeter);
+    let loads = wind_loads(4).with_units(units);
+    loads.to_compact_pickle(&path).unwrap();
+    let read = WindLoads::from_compact_pickle(&path);
+    std::fs::remove_file(&path).unwrap();
+    let read = read.unwrap();
+    assert_eq!(contents(&read), contents(&loads));
+    assert_eq!(read.time, loads.time);
+    assert_eq!(read.units(), units);
+}
+
+#[test]
+fn partial_pickle_round_trip() {
+    let path = std::env::temp_dir().join(format!("windloading-partial-{}.pkl", std::process::id()));
+    wind_loads(5).to_pickle(&path).unwrap();
+    let sources = WindLoads::from_pickle_sources(&path, &["OSS_Truss_6F"]);
+    let decimated = WindLoads::from_pickle_decimated(&path, 2);
+    std::fs::remove_file(&path).unwrap();
+    let sources = sources.unwrap();
+    let mut expected = contents(&wind_loads(5));
+    expected[0] = None;
+    assert_eq!(contents(&sources), expected);
+    assert_eq!(sources.time, wind_loads(5).time);
+    let decimated = decimated.unwrap();
+    let expected = wind_loads(5).decimate(2);
+    assert_eq!(contents(&decimated), contents(&expected));
+    assert_eq!(decimated.time, expected.time);
+}
+
+#[test]
+fn stream_round_trip() {
+    let path = std::env::temp_dir().join(format!("windloading-stream-{}.wlst", std::process::id()));
+    wind_loads(4).to_streamable(&path).unwrap();
+    let mut stream = windloading::WindLoadsStream::open(&path).unwrap();
+    assert_eq!(stream.n_sample(), 4);
+    assert_eq!(stream.sources(), vec!["OSS_TopEnd_6F", "OSS_Truss_6F"]);
+    let samples = stream.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(samples.len(), 4);
+    for (i, sample) in samples.iter().enumerate() {
+        match &sample[..] {
+            [IO::OSSTopEnd6F { data: Some(a) }, IO::OSSTruss6F { data: Some(b) }] => {
+                assert_eq!(a, &vec![i as f64; 6]);
+                assert_eq!(b, &vec![-(i as f64); 18]);
+            }
+            _ => panic!("expected the top-end and the truss loads"),
+        }
+    }
+    assert_eq!(stream.time(), wind_loads(4).time.last().copied());
+}
+
+#[test]
+fn time_follows_the_loads_after_range_and_decimate() {
+    let loads = wind_loads(20).range(0.1, 0.8);
+    assert_eq!(loads.time.len(), loads.n_samples().unwrap());
+    let loads = loads.decimate(3);
+    assert_eq!(loads.time.len(), loads.n_samples().unwrap());
+    for source in loads.loads.iter().flatten() {
+        assert_eq!(source.len(), loads.time.len());
+    }
+}
+
+#[test]
+fn time_is_truncated_to_n_sample() {
+    let n_sample = 6;
+    let loads = wind_loads(10).n_sample(n_sample).unwrap();
+    assert_eq!(loads.time.len(), n_sample);
+    let wind = loads.truss().unwrap().build().unwrap();
+    assert_eq!(wind.n_sample, n_sample);
+}
+
+#[test]
+fn old_m2_source_name_is_read() {
+    use serde_pickle::{HashableValue, Value};
+    use std::collections::BTreeMap;
+    let samples = |n: usize| {
+        Value::List(
+            (0..n)
+                .map(|i| Value::List(vec![Value::F64(i as f64); 42]))
+                .collect(),
+        )
+    };
+    let source = Value::Dict(BTreeMap::from([(
+        HashableValue::String("MC_M2_lcl_6F".to_string()),
+        samples(3),
+    )]));
+    let data = Value::Dict(BTreeMap::from([
+        (
+            HashableValue::String("outputs".to_string()),
+            Value::List(vec![Value::None, source]),
+        ),
+        (
+            HashableValue::String("time".to_string()),
+            Value::List((0..3).map(|i| Value::F64(i as f64 * 0.05)).collect()),
+        ),
+    ]));
+    let path = std::env::temp_dir().join(format!("windloading-old-m2-{}.pkl", std::process::id()));
+    let mut file = std::fs::File::create(&path).unwrap();
+    serde_pickle::value_to_writer(&mut file, &data, true).unwrap();
+    drop(file);
+    let loads = WindLoads::from_pickle(&path).unwrap();
+    let partial = WindLoads::from_pickle_sources(&path, &["MC_M2_lcl_force_6F"]);
+    let alias = WindLoads::from_pickle_sources(&path, &["MC_M2_lcl_6F"]);
+    std::fs::remove_file(&path).unwrap();
+    for loads in [loads, partial.unwrap(), alias.unwrap()] {
+        assert_eq!(loads.available_sources(), vec!["MC_M2_lcl_force_6F"]);
+        match &loads.loads[1] {
+            Some(Loads::MCM2Lcl6F(data)) => assert_eq!(data[2], vec![2f64; 42]),
+            _ => panic!("expected the M2 segments loads"),
+        }
+    }
+}
+
+#[test]
+fn loads_can_feed_other_fem_inputs() {
+    let mut wind = wind_loads(3)
+        .select_into("OSS_TopEnd_6F", dosio::io::jar::OSSM1Lcl::io())
+        .unwrap()
+        .select_into("OSS_Truss_6F", dosio::io::jar::MCM2Lcl::io())
+        .unwrap()
+        .build()
+        .unwrap();
+    let outputs = wind.outputs().unwrap();
+    assert!(matches!(outputs[0], IO::OSSM1Lcl { data: Some(_) }));
+    assert!(matches!(outputs[1], IO::MCM2Lcl { data: Some(_) }));
+}
+
+#[test]
+fn detrend_mean_returns_the_means() {
+    let (loads, means) = wind_loads(5).detrend_mean();
+    assert_eq!(means["OSS_TopEnd_6F"], vec![2f64; 6]);
+    assert_eq!(means["OSS_Truss_6F"], vec![-2f64; 18]);
+    match &loads.loads[0] {
+        Some(Loads::OSSTopEnd6F(data)) => assert_eq!(data[0], vec![-2f64; 6]),
+        _ => panic!("expected the top-end loads"),
+    }
+}
+
+#[test]
+fn layout_checks_the_number_of_bodies_of_the_source() {
+    let truss = Loads::OSSTruss6F(vec![vec![0f64; 18]; 2]);
+    assert_eq!(truss.layout().unwrap(), (3, 6));
+    let m1 = Loads::OSSM1Lcl6F(vec![vec![0f64; 36]; 2]);
+    assert!(matches!(
+        m1.layout(),
+        Err(WindLoadsError::SourceBodies {
+            expected: 7,
+            got: 6,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn superpose_converts_the_units() {
+    use windloading::{ForceUnit, MomentUnit, Units};
+    let kilo = Units::new(ForceUnit::KiloNewton, MomentUnit::KiloNewtonMeter);
+    let loads = wind_loads(2)
+        .with_units(kilo)
+        .superpose(&wind_loads(2))
+        .unwrap();
+    assert_eq!(loads.units(), kilo);
+    match &loads.loads[0] {
+        Some(Loads::OSSTopEnd6F(data)) => assert_eq!(data[1], vec![1.001; 6]),
+        _ => panic!("expected the top-end loads first"),
+    }
+}
+
+#[test]
+fn median_filter_removes_a_spike_next_to_the_edge() {
+    let topend = [1., 100., 1., 1.].iter().map(|&x| vec![x; 6]).collect();
+    let mut loads = Loads::OSSTopEnd6F(topend);
+    loads.median_filter(3);
+    assert!(loads.io().iter().all(|x| x == &vec![1f64; 6]));
+}
+
+#[test]
+fn truncated_stream_is_rejected() {
+    let path =
+        std::env::temp_dir().join(format!("windloading-truncated-{}.wlst", std::process::id()));
+    wind_loads(4).to_streamable(&path).unwrap();
+    let len = std::fs::metadata(&path).unwrap().len();
+    let file = std::fs::OpenOptions::new().write(true).open(&path).unwrap();
+    file.set_len(len - 8).unwrap();
+    drop(file);
+    let stream = windloading::WindLoadsStream::open(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(stream.err(), Some(WindLoadsError::InvalidStream)));
+}
+
+#[test]
+fn component_analyses_reject_invalid_arguments() {
+    let topend = Loads::OSSTopEnd6F((0..8).map(|i| vec![i as f64; 6]).collect());
+    assert!(matches!(
+        topend.psd(6, 20., 4).err(),
+        Some(WindLoadsError::Component { component: 6, .. })
+    ));
+    assert!(matches!(
+        topend.psd(0, 20., 16).err(),
+        Some(WindLoadsError::Nfft { nfft: 16, .. })
+    ));
+    assert!(topend.psd(0, 20., 4).is_ok());
+    assert!(topend.envelope(3, 6).is_err());
+    assert!(topend.rainflow(6).is_err());
+}
+
+#[test]
+fn select_available_skips_only_the_missing_sources() {
+    let loads = wind_loads(4).select_available().unwrap();
+    assert_eq!(loads.selected_tags().len(), 2);
+    assert!(!loads
+        .skipped_sources()
+        .iter()
+        .any(|x| x == "OSS_TopEnd_6F" || x == "OSS_Truss_6F"));
+    let time: Vec<f64> = (0..4).map(|i| i as f64 * 0.05).collect();
+    let short = WindLoads::from_loads(
+        time,
+        vec![
+            Some(Loads::OSSTopEnd6F(vec![vec![0f64; 6]; 4])),
+            Some(Loads::OSSTruss6F(vec![vec![0f64; 18]; 2])),
+        ],
+    );
+    let err = short.n_sample(4).unwrap().select_available().err();
+    assert!(matches!(err, Some(WindLoadsError::Mismatch { got: 2, .. })));
+}
+
+#[test]
+fn decimated_source_follows_the_time_vector() {
+    let time: Vec<f64> = (0..4).map(|i| i as f64 * 0.1).collect();
+    let truss = (0..8).map(|i| vec![i as f64; 18]).collect();
+    let loads = WindLoads::from_loads(
+        time.clone(),
+        vec![
+            Some(Loads::OSSTopEnd6F(vec![vec![0f64; 6]; 4])),
+            Some(Loads::OSSTruss6F(truss)),
+        ],
+    )
+    .decimate_source("OSS_Truss_6F", 2)
+    .unwrap();
+    match &loads.loads[1] {
+        Some(Loads::OSSTruss6F(data)) => {
+            assert_eq!(data.len(), loads.time.len());
+            assert_eq!(data[3], vec![6f64; 18]);
+        }
+        _ => panic!("expected the truss loads"),
+    }
+    let err = WindLoads::from_loads(time, vec![Some(Loads::OSSTruss6F(vec![vec![0f64; 18]; 4]))])
+        .decimate_source("OSS_Truss_6F", 2)
+        .err();
+    assert!(matches!(
+        err,
+        Some(WindLoadsError::Mismatch {
+            expected: 4,
+            got: 2,
+            ..
+        })
+    ));
+}